@@ -0,0 +1,159 @@
+use std::{
+    io::Read,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+
+use crate::{
+    input::{InputKind, InputSource},
+    solution::Day,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    pub warmup: usize,
+    pub runs: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            warmup: 1,
+            runs: 10,
+        }
+    }
+}
+
+/// Timing statistics over the measured runs of one step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn new(mut samples: Vec<Duration>) -> Stats {
+        assert!(!samples.is_empty(), "at least one run is required");
+        samples.sort();
+        // nearest-rank percentile
+        let rank = |p: usize| samples[(samples.len() * p).div_ceil(100).max(1) - 1];
+        Stats {
+            runs: samples.len(),
+            min: samples[0],
+            median: rank(50),
+            p95: rank(95),
+        }
+    }
+}
+
+/// Step of a day being timed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Step {
+    Parse,
+    Part(u8),
+}
+
+impl std::fmt::Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Parse => f.pad("parse"),
+            Step::Part(part) => f.pad(&format!("part{}", part)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DayBench {
+    pub day: u16,
+    pub steps: Vec<(Step, Stats)>,
+}
+
+impl DayBench {
+    /// Sum of the step medians.
+    pub fn total(&self) -> Duration {
+        self.steps.iter().map(|(_, stats)| stats.median).sum()
+    }
+}
+
+fn measure<T>(options: Options, mut f: impl FnMut() -> Result<T>) -> Result<Stats> {
+    for _ in 0..options.warmup {
+        f()?;
+    }
+    let mut samples = Vec::with_capacity(options.runs);
+    for _ in 0..options.runs.max(1) {
+        let start = Instant::now();
+        let out = f()?;
+        samples.push(start.elapsed());
+        drop(out);
+    }
+    Ok(Stats::new(samples))
+}
+
+/// Times parsing and each of the given parts of a day separately. The input
+/// is read into memory first so that file access is not part of the parse.
+pub fn bench_day(
+    day: &Day,
+    source: &InputSource,
+    kind: InputKind,
+    parts: &[u8],
+    options: Options,
+) -> Result<DayBench> {
+    let mut text = String::new();
+    source.open(day.day, kind)?.read_to_string(&mut text)?;
+    let source = InputSource::Memory(text);
+
+    let mut steps = vec![];
+    steps.push((Step::Parse, measure(options, || day.parse(&source, kind))?));
+
+    let input = day.parse(&source, kind)?;
+    for &part in parts {
+        steps.push((Step::Part(part), measure(options, || input.run(part))?));
+    }
+
+    Ok(DayBench {
+        day: day.day,
+        steps,
+    })
+}
+
+/// Prints per-step statistics followed by a per-day summary of medians.
+pub fn print_table(benches: &[DayBench]) {
+    println!(
+        "{:<6} {:<6} {:>5} {:>12} {:>12} {:>12}",
+        "day", "step", "runs", "min", "median", "p95"
+    );
+    for bench in benches {
+        for (step, stats) in &bench.steps {
+            println!(
+                "day{:0>2} {:<6} {:>5} {:>12} {:>12} {:>12}",
+                bench.day,
+                step,
+                stats.runs,
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.p95),
+            );
+        }
+    }
+
+    let total: Duration = benches.iter().map(DayBench::total).sum();
+    println!();
+    println!("{:<6} {:>12} {:>7}", "day", "median", "share");
+    for bench in benches {
+        let share = if total.is_zero() {
+            0.0
+        } else {
+            bench.total().as_secs_f64() / total.as_secs_f64() * 100.0
+        };
+        println!(
+            "day{:0>2} {:>12} {:>6.1}%",
+            bench.day,
+            format!("{:.2?}", bench.total()),
+            share
+        );
+    }
+    println!("{:<6} {:>12}", "total", format!("{:.2?}", total));
+}
@@ -0,0 +1,367 @@
+use std::{env, io, path::PathBuf, process::ExitCode, thread, time::Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use rust::{
+    bench::{self, bench_day},
+    days::{day01, find, DAYS},
+    history::{self, compare, History},
+    input::{InputKind, InputSource},
+    report::{Format, Reporter},
+    runner::{run_day, run_parallel},
+    solution::Day,
+    verify::{Manifest, Status, MANIFEST},
+};
+
+const USAGE: &str = "Usage: aoc [verify|bench] <DAY|all> [--part 1|2] [--sample[=N]] \
+                     [--input-dir DIR | --input FILE|-] [--runs N] [--warmup N] \
+                     [--no-save] [--history FILE] [--parallel | --jobs N] \
+                     [--format plain|json|csv]\n       \
+                     aoc compare [--baseline COMMIT] [--threshold PCT] [--history FILE]\n       \
+                     aoc stream 1 [--input-dir DIR | --input FILE|-]\n       \
+                     aoc list";
+
+enum Command {
+    List,
+    Run,
+    Verify,
+    Bench,
+    Compare,
+    Stream,
+}
+
+struct Args {
+    command: Command,
+    /// `None` selects every registered day.
+    day: Option<u16>,
+    part: Option<u8>,
+    source: InputSource,
+    kind: InputKind,
+    bench: bench::Options,
+    save: bool,
+    history: PathBuf,
+    baseline: Option<String>,
+    /// Slowdown in percent above which a step counts as a regression.
+    threshold: f64,
+    /// Number of threads to run days on, `None` running them one by one.
+    jobs: Option<usize>,
+    format: Format,
+}
+
+fn parse_args() -> Result<Args> {
+    let mut command = Command::Run;
+    let mut day = None;
+    let mut all = false;
+    let mut part = None;
+    let mut source = InputSource::Search;
+    let mut kind = InputKind::Real;
+    let mut bench = bench::Options::default();
+    let mut save = true;
+    let mut history = history::default_path();
+    let mut baseline = None;
+    let mut threshold = 10.0;
+    let mut jobs = None;
+    let mut format = Format::Plain;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => {
+                let value = args.next().context("--part requires a value")?;
+                match value.as_str() {
+                    "1" => part = Some(1),
+                    "2" => part = Some(2),
+                    _ => bail!("invalid part `{}`, expected 1 or 2", value),
+                }
+            }
+            "--sample" | "-s" => kind = InputKind::Sample(1),
+            _ if arg.starts_with("--sample=") => {
+                kind = format!("sample{}", &arg["--sample=".len()..]).parse()?;
+            }
+            "--input-dir" => {
+                let value = args.next().context("--input-dir requires a value")?;
+                source = InputSource::Dir(PathBuf::from(value));
+            }
+            "--input" | "-i" => {
+                let value = args.next().context("--input requires a value")?;
+                source = match value.as_str() {
+                    "-" => InputSource::Stdin,
+                    _ => InputSource::File(PathBuf::from(value)),
+                };
+            }
+            "--runs" => {
+                let value = args.next().context("--runs requires a value")?;
+                bench.runs = value.parse().context("invalid --runs")?;
+            }
+            "--warmup" => {
+                let value = args.next().context("--warmup requires a value")?;
+                bench.warmup = value.parse().context("invalid --warmup")?;
+            }
+            "--no-save" => save = false,
+            "--history" => {
+                let value = args.next().context("--history requires a value")?;
+                history = PathBuf::from(value);
+            }
+            "--baseline" => {
+                baseline = Some(args.next().context("--baseline requires a value")?);
+            }
+            "--threshold" => {
+                let value = args.next().context("--threshold requires a value")?;
+                threshold = value.parse().context("invalid --threshold")?;
+            }
+            "--parallel" => {
+                jobs = Some(thread::available_parallelism().map_or(1, |n| n.get()));
+            }
+            "--jobs" | "-j" => {
+                let value = args.next().context("--jobs requires a value")?;
+                jobs = Some(value.parse().context("invalid --jobs")?);
+            }
+            "--format" | "-f" => {
+                format = args.next().context("--format requires a value")?.parse()?;
+            }
+            "--json" => format = Format::Json,
+            "--csv" => format = Format::Csv,
+            "list" => command = Command::List,
+            "verify" => command = Command::Verify,
+            "bench" => command = Command::Bench,
+            "compare" => command = Command::Compare,
+            "stream" => command = Command::Stream,
+            "all" => all = true,
+            value => {
+                let value = value
+                    .parse::<u16>()
+                    .with_context(|| format!("invalid day `{}`\n{}", value, USAGE))?;
+                day = Some(value);
+            }
+        }
+    }
+
+    match command {
+        Command::Run if day.is_none() && !all => bail!(USAGE),
+        Command::Stream if day.is_none() => bail!(USAGE),
+        Command::List | Command::Compare => {}
+        _ if day.is_none() && matches!(source, InputSource::File(_) | InputSource::Stdin) => {
+            bail!("--input can only be used with a single day")
+        }
+        Command::Verify if matches!(source, InputSource::File(_) | InputSource::Stdin) => {
+            bail!("verify reads answers next to the inputs, use --input-dir instead of --input")
+        }
+        _ => {}
+    }
+
+    Ok(Args {
+        command,
+        day,
+        part,
+        source,
+        kind,
+        bench,
+        save,
+        history,
+        baseline,
+        threshold,
+        jobs,
+        format,
+    })
+}
+
+impl Args {
+    fn days(&self) -> Result<Vec<&'static Day>> {
+        match self.day {
+            Some(n) => {
+                let day = find(n).with_context(|| format!("day {} is not implemented", n))?;
+                Ok(vec![day])
+            }
+            None => Ok(DAYS.iter().collect()),
+        }
+    }
+
+    fn parts(&self) -> Vec<u8> {
+        match self.part {
+            Some(part) => vec![part],
+            None => vec![1, 2],
+        }
+    }
+}
+
+/// Runs the selected days, returning whether all of them succeeded.
+fn run(args: &Args, reporter: &mut dyn Reporter) -> Result<bool> {
+    let days = args.days()?;
+    let parts = args.parts();
+
+    let mut ok = true;
+    match args.jobs {
+        Some(jobs) => {
+            for result in run_parallel(&days, &args.source, args.kind, &parts, jobs) {
+                ok &= result.is_ok();
+                reporter.report(&result);
+            }
+        }
+        None => {
+            for day in days {
+                let result = run_day(day, &args.source, args.kind, &parts);
+                ok &= result.is_ok();
+                reporter.report(&result);
+            }
+        }
+    }
+    Ok(ok)
+}
+
+/// Checks every selected day against its manifest, returning whether all
+/// known answers matched.
+fn verify(args: &Args) -> Result<bool> {
+    let mut ok = true;
+
+    for day in args.days()? {
+        let manifest = args
+            .source
+            .day_dir(day.day)
+            .and_then(|dir| Manifest::load(&dir.join(MANIFEST)));
+        let manifest = match manifest {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                ok = false;
+                println!(
+                    "day{:0>2} {:<8} {:<5} {:<7} {:#}",
+                    day.day,
+                    MANIFEST,
+                    "",
+                    Status::Error(e.to_string()),
+                    e
+                );
+                continue;
+            }
+        };
+
+        for kind in manifest.kinds() {
+            let input = day.parse(&args.source, kind);
+            for part in args.parts() {
+                let actual = match &input {
+                    Ok(input) => input.run(part),
+                    Err(e) => Err(anyhow!("{}", e)),
+                };
+                let status = manifest.check(kind, part, &actual);
+                ok &= !status.is_failure();
+
+                let detail = match (&status, &actual) {
+                    (Status::Fail { expected, actual }, _) => {
+                        format!("got {}, expected {}", actual, expected)
+                    }
+                    (Status::Error(e), _) => e.clone(),
+                    (_, Ok(actual)) => actual.to_string(),
+                    (_, Err(_)) => String::new(),
+                };
+                println!(
+                    "day{:0>2} {:<8} part{} {:<7} {}",
+                    day.day, kind, part, status, detail
+                );
+            }
+        }
+    }
+
+    Ok(ok)
+}
+
+/// Compares the latest saved benchmark run against the previous one, or the
+/// latest run at `--baseline`, returning whether no step regressed.
+fn compare_runs(args: &Args) -> Result<bool> {
+    let history = History::load(&args.history)?;
+    let runs = history.runs();
+    let current = *runs
+        .last()
+        .with_context(|| format!("no benchmark runs in {}", args.history.display()))?;
+    let baseline = match &args.baseline {
+        Some(commit) => history
+            .latest_at(commit, current)
+            .with_context(|| format!("no benchmark run at commit {}", commit))?,
+        None => *runs
+            .iter()
+            .rev()
+            .nth(1)
+            .context("only one benchmark run, nothing to compare against")?,
+    };
+
+    let current = history.run(current);
+    let baseline = history.run(baseline);
+    println!(
+        "comparing {} against {} (threshold {}%)",
+        current[0].commit, baseline[0].commit, args.threshold
+    );
+
+    let mut ok = true;
+    for cmp in compare(&baseline, &current) {
+        let regression = cmp.is_regression(args.threshold);
+        ok &= !regression;
+        let line = format!(
+            "day{:0>2} {:<8} {:<6} {:>12} {:>12} {:>+8.1}% {}",
+            cmp.day,
+            cmp.kind,
+            cmp.step,
+            format!("{:.2?}", cmp.baseline),
+            format!("{:.2?}", cmp.current),
+            cmp.change(),
+            if regression { "SLOWER" } else { "" }
+        );
+        println!("{}", line.trim_end());
+    }
+    Ok(ok)
+}
+
+/// Feeds the input to a day's streaming mode, which prints its answers
+/// after every line.
+fn stream(args: &Args) -> Result<()> {
+    let day = args.day.context("stream requires a day")?;
+    let lines = args.source.lines(day, args.kind)?;
+    match day {
+        1 => day01::stream(lines, io::stdout().lock()),
+        _ => bail!("day {} has no streaming mode", day),
+    }
+}
+
+fn main() -> Result<ExitCode> {
+    let args = parse_args()?;
+
+    match args.command {
+        Command::List => {
+            for day in DAYS {
+                println!("day{:0>2}", day.day);
+            }
+        }
+        Command::Run => {
+            let mut reporter =
+                args.format
+                    .reporter(&args.parts(), args.day.is_none(), args.jobs.is_some());
+            let start = Instant::now();
+            let ok = run(&args, reporter.as_mut())?;
+            reporter.finish(start.elapsed());
+            if !ok {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Verify => {
+            if !verify(&args)? {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Bench => {
+            let benches = args
+                .days()?
+                .into_iter()
+                .map(|day| bench_day(day, &args.source, args.kind, &args.parts(), args.bench))
+                .collect::<Result<Vec<_>>>()?;
+            bench::print_table(&benches);
+            if args.save {
+                History::append(&args.history, &history::git_commit(), args.kind, &benches)?;
+            }
+        }
+        Command::Compare => {
+            if !compare_runs(&args)? {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Stream => stream(&args)?,
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,278 @@
+use crate::{
+    geom::{Direction, Point},
+    grid::Pos,
+};
+
+/// How the keys of a `BitSet` map to bit indices `0..size()`.
+pub trait Layout: Clone + PartialEq {
+    type Key;
+
+    fn size(&self) -> usize;
+
+    /// Bit of `key`, `None` if the set cannot hold it.
+    fn index(&self, key: Self::Key) -> Option<usize>;
+
+    fn key(&self, index: usize) -> Self::Key;
+}
+
+/// A fixed-size set of keys, one bit each, packed into 64-bit words.
+/// Keys outside the layout are never contained, and panic on insert.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitSet<L> {
+    layout: L,
+    words: Vec<u64>,
+}
+
+impl<L: Layout> BitSet<L> {
+    pub fn with_layout(layout: L) -> BitSet<L> {
+        BitSet {
+            words: vec![0; layout.size().div_ceil(64)],
+            layout,
+        }
+    }
+
+    pub fn layout(&self) -> &L {
+        &self.layout
+    }
+
+    pub fn contains(&self, key: L::Key) -> bool {
+        self.layout
+            .index(key)
+            .is_some_and(|i| self.words[i / 64] & (1 << (i % 64)) != 0)
+    }
+
+    /// Adds `key`, returning whether it was not in the set yet.
+    pub fn insert(&mut self, key: L::Key) -> bool {
+        let i = self.layout.index(key).expect("key outside of the set");
+        let (word, bit) = (&mut self.words[i / 64], 1 << (i % 64));
+        let new = *word & bit == 0;
+        *word |= bit;
+        new
+    }
+
+    /// Removes `key`, returning whether it was in the set.
+    pub fn remove(&mut self, key: L::Key) -> bool {
+        let Some(i) = self.layout.index(key) else {
+            return false;
+        };
+        let (word, bit) = (&mut self.words[i / 64], 1 << (i % 64));
+        let present = *word & bit != 0;
+        *word &= !bit;
+        present
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Number of keys in the set.
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Adds every key of `other`, which must have the same layout.
+    pub fn union_with(&mut self, other: &BitSet<L>) {
+        assert!(self.layout == other.layout, "layouts differ");
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a |= b;
+        }
+    }
+
+    /// Keeps only the keys also in `other`, which must have the same layout.
+    pub fn intersect_with(&mut self, other: &BitSet<L>) {
+        assert!(self.layout == other.layout, "layouts differ");
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a &= b;
+        }
+    }
+
+    /// Keys in the set, in bit order.
+    pub fn iter(&self) -> impl Iterator<Item = L::Key> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(self.layout.key(i * 64 + bit))
+            })
+        })
+    }
+}
+
+/// Cells of a `height` by `width` grid, row by row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cells {
+    pub height: usize,
+    pub width: usize,
+}
+
+impl Layout for Cells {
+    type Key = Pos;
+
+    fn size(&self) -> usize {
+        self.height * self.width
+    }
+
+    fn index(&self, Point { x, y }: Pos) -> Option<usize> {
+        let (x, y) = (usize::try_from(x).ok()?, usize::try_from(y).ok()?);
+        (x < self.height && y < self.width).then(|| x * self.width + y)
+    }
+
+    fn key(&self, index: usize) -> Pos {
+        Point::new((index / self.width) as i32, (index % self.width) as i32)
+    }
+}
+
+/// `(cell, direction)` states of a grid, by cell then by direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct States(pub Cells);
+
+impl Layout for States {
+    type Key = (Pos, Direction);
+
+    fn size(&self) -> usize {
+        self.0.size() * Direction::ALL.len()
+    }
+
+    fn index(&self, (pos, direction): (Pos, Direction)) -> Option<usize> {
+        Some(self.0.index(pos)? * Direction::ALL.len() + direction as usize)
+    }
+
+    fn key(&self, index: usize) -> (Pos, Direction) {
+        let n = Direction::ALL.len();
+        (self.0.key(index / n), Direction::ALL[index % n])
+    }
+}
+
+/// A set of cells of a grid.
+pub type BitGrid = BitSet<Cells>;
+
+/// A set of `(cell, direction)` states of a grid, e.g. for spotting a
+/// walker that comes back the same way.
+pub type StateSet = BitSet<States>;
+
+impl BitGrid {
+    pub fn new(height: usize, width: usize) -> BitGrid {
+        BitSet::with_layout(Cells { height, width })
+    }
+}
+
+impl StateSet {
+    pub fn new(height: usize, width: usize) -> StateSet {
+        BitSet::with_layout(States(Cells { height, width }))
+    }
+
+    /// Cells in at least one state.
+    pub fn cells(&self) -> BitGrid {
+        let mut cells = BitSet::with_layout(self.layout.0);
+        for (pos, _) in self.iter() {
+            cells.insert(pos);
+        }
+        cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_remove_contains() {
+        let mut set = BitGrid::new(3, 70);
+        assert!(set.is_empty());
+        assert!(set.insert(Point::new(2, 69)));
+        assert!(!set.insert(Point::new(2, 69)));
+        assert!(set.insert(Point::new(0, 0)));
+        assert!(set.contains(Point::new(2, 69)));
+        assert!(!set.contains(Point::new(1, 69)));
+        assert!(!set.contains(Point::new(-1, 0)));
+        assert!(!set.contains(Point::new(0, 70)));
+        assert_eq!(set.len(), 2);
+
+        assert!(set.remove(Point::new(0, 0)));
+        assert!(!set.remove(Point::new(0, 0)));
+        assert!(!set.remove(Point::new(5, 5)));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![Point::new(2, 69)]);
+
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "key outside of the set")]
+    fn insert_outside_panics() {
+        BitGrid::new(2, 2).insert(Point::new(2, 0));
+    }
+
+    #[test]
+    fn iterates_in_reading_order() {
+        let cells = [
+            Point::new(1, 0),
+            Point::new(0, 3),
+            Point::new(9, 9),
+            Point::new(0, 1),
+        ];
+        let mut set = BitGrid::new(10, 10);
+        for &c in &cells {
+            set.insert(c);
+        }
+        let mut sorted = cells.to_vec();
+        sorted.sort();
+        assert_eq!(set.iter().collect::<Vec<_>>(), sorted);
+    }
+
+    #[test]
+    fn union_and_intersection() {
+        let mut a = BitGrid::new(8, 8);
+        let mut b = BitGrid::new(8, 8);
+        for i in 0..8 {
+            a.insert(Point::new(i, i));
+            b.insert(Point::new(i, 7 - i));
+        }
+        let mut both = a.clone();
+        both.intersect_with(&b);
+        assert_eq!(both.len(), 0);
+
+        b.insert(Point::new(3, 3));
+        let mut both = a.clone();
+        both.intersect_with(&b);
+        assert_eq!(both.iter().collect::<Vec<_>>(), vec![Point::new(3, 3)]);
+
+        a.union_with(&b);
+        assert_eq!(a.len(), 16);
+    }
+
+    #[test]
+    #[should_panic(expected = "layouts differ")]
+    fn union_of_different_sizes_panics() {
+        BitGrid::new(2, 3).union_with(&BitGrid::new(3, 2));
+    }
+
+    #[test]
+    fn states_keep_directions_apart() {
+        let mut set = StateSet::new(4, 4);
+        let pos = Point::new(1, 2);
+        assert!(set.insert((pos, Direction::Up)));
+        assert!(set.insert((pos, Direction::Left)));
+        assert!(!set.insert((pos, Direction::Up)));
+        assert!(!set.contains((pos, Direction::Down)));
+        assert!(set.insert((Point::new(3, 3), Direction::UpLeft)));
+        assert_eq!(set.len(), 3);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![
+                (pos, Direction::Up),
+                (pos, Direction::Left),
+                (Point::new(3, 3), Direction::UpLeft),
+            ]
+        );
+        assert_eq!(set.cells().len(), 2);
+    }
+}
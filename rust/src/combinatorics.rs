@@ -0,0 +1,183 @@
+/// Every sequence of choices where position `i` picks one of `radices[i]`
+/// options, in lexicographic order. The sequence is lent out by `next`
+/// rather than yielded by an `Iterator`, so enumerating does not allocate.
+#[derive(Debug, Clone)]
+pub struct Sequences {
+    radices: Vec<usize>,
+    digits: Vec<usize>,
+    /// Position to increment on the next call, `None` before the first one.
+    carry_from: Option<usize>,
+    done: bool,
+}
+
+impl Sequences {
+    pub fn new(radices: Vec<usize>) -> Sequences {
+        Sequences {
+            digits: vec![0; radices.len()],
+            done: radices.contains(&0),
+            radices,
+            carry_from: None,
+        }
+    }
+
+    /// Sequences of length `len` over `k` options each.
+    pub fn uniform(k: usize, len: usize) -> Sequences {
+        Sequences::new(vec![k; len])
+    }
+
+    pub fn len(&self) -> usize {
+        self.radices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.radices.is_empty()
+    }
+
+    /// Number of sequences in total, `None` if it overflows.
+    pub fn count(&self) -> Option<usize> {
+        self.radices
+            .iter()
+            .try_fold(1usize, |acc, &radix| acc.checked_mul(radix))
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<&[usize]> {
+        if self.done {
+            return None;
+        }
+        match self.carry_from.replace(self.len().wrapping_sub(1)) {
+            None => {}
+            Some(pos) if pos >= self.len() => {
+                self.done = true;
+                return None;
+            }
+            Some(pos) => {
+                let Some(i) = (0..=pos)
+                    .rev()
+                    .find(|&i| self.digits[i] + 1 < self.radices[i])
+                else {
+                    self.done = true;
+                    return None;
+                };
+                self.digits[i] += 1;
+                self.digits[i + 1..].fill(0);
+            }
+        }
+        Some(&self.digits)
+    }
+
+    /// Skips every remaining sequence sharing the first `len` choices of the
+    /// one `next` last returned, e.g. because that prefix already failed.
+    pub fn prune(&mut self, len: usize) {
+        if len == 0 {
+            self.done = true;
+            return;
+        }
+        if let Some(pos) = self.carry_from {
+            self.carry_from = Some(pos.min(len - 1));
+        }
+    }
+}
+
+/// Every pair of an item of `outer` with one of `inner`, `inner` varying
+/// fastest. Only the `inner` iterator is cloned, never collected.
+pub fn product<I, J>(outer: I, inner: J) -> impl Iterator<Item = (I::Item, J::Item)>
+where
+    I: IntoIterator,
+    I::Item: Clone,
+    J: IntoIterator,
+    J::IntoIter: Clone,
+{
+    let inner = inner.into_iter();
+    outer
+        .into_iter()
+        .flat_map(move |a| inner.clone().map(move |b| (a.clone(), b)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(mut sequences: Sequences) -> Vec<Vec<usize>> {
+        let mut all = vec![];
+        while let Some(seq) = sequences.next() {
+            all.push(seq.to_vec());
+        }
+        all
+    }
+
+    #[test]
+    fn mixed_radix_order() {
+        let sequences = Sequences::new(vec![2, 1, 3]);
+        assert_eq!(sequences.count(), Some(6));
+        assert_eq!(
+            collect(sequences),
+            vec![
+                vec![0, 0, 0],
+                vec![0, 0, 1],
+                vec![0, 0, 2],
+                vec![1, 0, 0],
+                vec![1, 0, 1],
+                vec![1, 0, 2],
+            ]
+        );
+        assert_eq!(collect(Sequences::uniform(3, 4)).len(), 81);
+    }
+
+    #[test]
+    fn degenerate_radices() {
+        assert_eq!(collect(Sequences::new(vec![])), vec![Vec::<usize>::new()]);
+        assert_eq!(Sequences::new(vec![]).count(), Some(1));
+        assert!(collect(Sequences::new(vec![3, 0, 2])).is_empty());
+        assert_eq!(Sequences::new(vec![3, 0, 2]).count(), Some(0));
+        assert_eq!(Sequences::uniform(usize::MAX, 2).count(), None);
+    }
+
+    #[test]
+    fn prune_skips_prefixes() {
+        // Drop every sequence whose first two choices sum to 3, and every
+        // one starting with 2, pruning as soon as the prefix is seen.
+        let bad = |seq: &[usize]| -> Option<usize> {
+            if seq[0] == 2 {
+                Some(1)
+            } else if seq[0] + seq[1] == 3 {
+                Some(2)
+            } else {
+                None
+            }
+        };
+        let mut sequences = Sequences::uniform(3, 3);
+        let mut kept = vec![];
+        while let Some(seq) = sequences.next() {
+            match bad(seq) {
+                Some(len) => sequences.prune(len),
+                None => kept.push(seq.to_vec()),
+            }
+        }
+        let expected: Vec<_> = collect(Sequences::uniform(3, 3))
+            .into_iter()
+            .filter(|seq| bad(seq).is_none())
+            .collect();
+        assert_eq!(kept, expected);
+    }
+
+    #[test]
+    fn prune_whole_and_nothing() {
+        let mut sequences = Sequences::uniform(2, 2);
+        assert_eq!(sequences.next(), Some(&[0, 0][..]));
+        sequences.prune(2);
+        assert_eq!(sequences.next(), Some(&[0, 1][..]));
+        sequences.prune(0);
+        assert_eq!(sequences.next(), None);
+    }
+
+    #[test]
+    fn product_pairs_inner_fastest() {
+        let pairs: Vec<_> = product(['a', 'b'], 0..3).collect();
+        assert_eq!(
+            pairs,
+            vec![('a', 0), ('a', 1), ('a', 2), ('b', 0), ('b', 1), ('b', 2)]
+        );
+        assert_eq!(product(0..3, 0..0).count(), 0);
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::{btree_map, BTreeMap};
+
+/// A multiset: how many times each key occurs, in key order. Keys with a
+/// count of zero are never stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter<K> {
+    counts: BTreeMap<K, u64>,
+}
+
+impl<K: Ord> Default for Counter<K> {
+    fn default() -> Counter<K> {
+        Counter::new()
+    }
+}
+
+impl<K: Ord> Counter<K> {
+    pub fn new() -> Counter<K> {
+        Counter {
+            counts: BTreeMap::new(),
+        }
+    }
+
+    /// Number of distinct keys.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Sum of all counts.
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    pub fn get(&self, key: &K) -> u64 {
+        self.counts.get(key).copied().unwrap_or(0)
+    }
+
+    pub fn add(&mut self, key: K, n: u64) {
+        if n > 0 {
+            *self.counts.entry(key).or_insert(0) += n;
+        }
+    }
+
+    /// Takes up to `n` occurrences of `key` away, returning how many were.
+    pub fn remove(&mut self, key: &K, n: u64) -> u64 {
+        let Some(count) = self.counts.get_mut(key) else {
+            return 0;
+        };
+        let removed = n.min(*count);
+        *count -= removed;
+        if *count == 0 {
+            self.counts.remove(key);
+        }
+        removed
+    }
+
+    /// Adds every count of `other`.
+    pub fn merge(&mut self, other: Counter<K>) {
+        for (key, n) in other.counts {
+            self.add(key, n);
+        }
+    }
+
+    /// Multiplies every count by `factor`.
+    pub fn scale(&mut self, factor: u64) {
+        if factor == 0 {
+            self.counts.clear();
+        } else {
+            self.counts.values_mut().for_each(|n| *n *= factor);
+        }
+    }
+
+    /// Keys with their counts, most frequent first, ties in key order.
+    pub fn most_common(&self) -> Vec<(&K, u64)> {
+        let mut entries: Vec<_> = self.iter().collect();
+        entries.sort_by_key(|&(_, n)| std::cmp::Reverse(n));
+        entries
+    }
+
+    /// Keys with their counts, in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, u64)> {
+        self.counts.iter().map(|(key, &n)| (key, n))
+    }
+
+    /// Replaces each key by the `(successor, weight)` pairs from `next`, a
+    /// key counted `n` times adding `n * weight` to each of its successors.
+    pub fn step<I>(&self, mut next: impl FnMut(&K) -> I) -> Counter<K>
+    where
+        I: IntoIterator<Item = (K, u64)>,
+    {
+        let mut result = Counter::new();
+        for (key, &n) in &self.counts {
+            for (successor, weight) in next(key) {
+                result.add(successor, n * weight);
+            }
+        }
+        result
+    }
+}
+
+impl<K: Ord> FromIterator<K> for Counter<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Counter<K> {
+        let mut counter = Counter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<K: Ord> Extend<K> for Counter<K> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            self.add(key, 1);
+        }
+    }
+}
+
+impl<K> IntoIterator for Counter<K> {
+    type Item = (K, u64);
+    type IntoIter = btree_map::IntoIter<K, u64>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_and_remove() {
+        let mut counter: Counter<&str> = ["a", "b", "a"].into_iter().collect();
+        assert_eq!((counter.get(&"a"), counter.get(&"c")), (2, 0));
+        counter.add("c", 0);
+        assert_eq!(counter.len(), 2);
+        assert_eq!(counter.remove(&"a", 5), 2);
+        assert_eq!(counter.remove(&"a", 1), 0);
+        assert_eq!(counter.remove(&"b", 0), 0);
+        assert_eq!(counter.iter().collect::<Vec<_>>(), vec![(&"b", 1)]);
+        assert_eq!(counter.remove(&"b", 1), 1);
+        assert!(counter.is_empty());
+    }
+
+    #[test]
+    fn merge_scale_and_totals() {
+        let mut counter: Counter<u8> = [1, 2, 2].into_iter().collect();
+        counter.merge([2, 3].into_iter().collect());
+        assert_eq!(counter.total(), 5);
+        counter.scale(3);
+        assert_eq!(
+            counter.clone().into_iter().collect::<Vec<_>>(),
+            vec![(1, 3), (2, 9), (3, 3)]
+        );
+        assert_eq!(counter.most_common(), vec![(&2, 9), (&1, 3), (&3, 3)]);
+        counter.scale(0);
+        assert!(counter.is_empty());
+    }
+
+    #[test]
+    fn step_spreads_counts() {
+        // Each stone n becomes n + 1 and n * 2, as in a blink of day 11.
+        let counter: Counter<u64> = [1, 1, 2].into_iter().collect();
+        let next = counter.step(|&n| [(n + 1, 1), (n * 2, 1)]);
+        assert_eq!(
+            next.into_iter().collect::<Vec<_>>(),
+            vec![(2, 4), (3, 1), (4, 1)]
+        );
+        let weighted = counter.step(|&n| [(n % 2, 10)]);
+        assert_eq!(
+            weighted.iter().collect::<Vec<_>>(),
+            vec![(&0, 10), (&1, 20)]
+        );
+    }
+}
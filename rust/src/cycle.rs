@@ -0,0 +1,176 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Where the sequence `x0, step(x0), step(step(x0)), ...` starts repeating:
+/// state `start + length` is the first one equal to an earlier state, the
+/// one at `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Smallest index holding the same state as index `n`, so the state
+    /// after `n` steps can be found without running them all.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Runs `step` `n` times from `state`, `None` if the sequence ends first.
+fn advance<S>(state: &S, n: usize, step: &mut impl FnMut(&S) -> Option<S>) -> Option<S>
+where
+    S: Clone,
+{
+    let mut state = state.clone();
+    for _ in 0..n {
+        state = step(&state)?;
+    }
+    Some(state)
+}
+
+/// Floyd's tortoise and hare. `step` returns `None` when the simulation
+/// ends, in which case there is no cycle. Uses constant memory.
+pub fn floyd<S: Clone + Eq>(x0: S, mut step: impl FnMut(&S) -> Option<S>) -> Option<Cycle> {
+    let mut tortoise = step(&x0)?;
+    let mut hare = advance(&x0, 2, &mut step)?;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = advance(&hare, 2, &mut step)?;
+    }
+
+    let mut start = 0;
+    let mut tortoise = x0;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = step(&tortoise)?;
+    while tortoise != hare {
+        hare = step(&hare)?;
+        length += 1;
+    }
+
+    Some(Cycle { start, length })
+}
+
+/// Brent's algorithm, same contract as `floyd` but usually fewer steps.
+pub fn brent<S: Clone + Eq>(x0: S, mut step: impl FnMut(&S) -> Option<S>) -> Option<Cycle> {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = step(&x0)?;
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare)?;
+        length += 1;
+    }
+
+    let mut start = 0;
+    let mut tortoise = x0.clone();
+    let mut hare = advance(&x0, length, &mut step)?;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    Some(Cycle { start, length })
+}
+
+/// Remembers every state seen, so each is stepped only once. Faster than
+/// `floyd` and `brent` when `step` is expensive, at the cost of memory.
+pub fn hashed<S: Clone + Eq + Hash>(x0: S, mut step: impl FnMut(&S) -> Option<S>) -> Option<Cycle> {
+    let mut seen = HashMap::new();
+    let mut state = x0;
+    for i in 0.. {
+        if let Some(&start) = seen.get(&state) {
+            return Some(Cycle {
+                start,
+                length: i - start,
+            });
+        }
+        let next = step(&state)?;
+        seen.insert(state, i);
+        state = next;
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Detects with all three, checking they agree.
+    fn detect(x0: u64, step: impl Fn(&u64) -> Option<u64>) -> Option<Cycle> {
+        let cycle = floyd(x0, &step);
+        assert_eq!(brent(x0, &step), cycle);
+        assert_eq!(hashed(x0, &step), cycle);
+        cycle
+    }
+
+    #[test]
+    fn every_tail_and_loop_length() {
+        for start in 0..10 {
+            for length in 1..10 {
+                let end = start + length;
+                let step = |&i: &u64| Some(if i + 1 < end { i + 1 } else { start });
+                assert_eq!(
+                    detect(0, step),
+                    Some(Cycle {
+                        start: start as usize,
+                        length: length as usize,
+                    })
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn pseudo_random_sequence() {
+        let step = |&x: &u64| Some((x * x + 1) % 1009);
+        let cycle = detect(2, step).unwrap();
+        let mut states = vec![2];
+        while states.len() <= cycle.start + cycle.length {
+            states.push(step(states.last().unwrap()).unwrap());
+        }
+        assert_eq!(states[cycle.start], states[cycle.start + cycle.length]);
+        // Every state before the repeat is new.
+        let repeat = states[cycle.start + cycle.length];
+        assert_eq!(states.iter().position(|&x| x == repeat), Some(cycle.start));
+        let mut distinct = states[..cycle.start + cycle.length].to_vec();
+        distinct.sort();
+        distinct.dedup();
+        assert_eq!(distinct.len(), cycle.start + cycle.length);
+    }
+
+    #[test]
+    fn ended_sequences_have_no_cycle() {
+        for end in 0..10 {
+            assert_eq!(detect(0, |&i| (i < end).then_some(i + 1)), None);
+        }
+    }
+
+    #[test]
+    fn reduce_skips_whole_loops() {
+        let cycle = Cycle {
+            start: 3,
+            length: 4,
+        };
+        assert_eq!(cycle.reduce(2), 2);
+        assert_eq!(cycle.reduce(3), 3);
+        assert_eq!(cycle.reduce(7), 3);
+        assert_eq!(cycle.reduce(1_000_000_001), 5);
+    }
+}
@@ -0,0 +1,507 @@
+use std::io::Write;
+
+use anyhow::{Context, Result};
+
+use crate::{
+    answer::Answer,
+    counter::Counter,
+    error::{Line, ParseError},
+    input::Reader,
+    parser::{
+        self, alt, literal, optional, pair, separated_pair, signed, space1, terminated, value,
+    },
+    solution::Solution,
+};
+
+pub struct Day01;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// Both lists of location IDs, with their total distance and similarity
+/// score kept up to date as IDs come and go.
+///
+/// The distance pairs the lists in sorted order, which one insertion can
+/// shift entirely. Instead it is tracked as the sum over every value `t` of
+/// `|D(t)|`, `D(t)` being how many more left than right IDs are at most `t`.
+/// Adding an ID adds 1 or -1 to `D` from its value on, which `BalanceTree`
+/// applies in logarithmic time, plus logarithmic time for each stretch past
+/// the ID over which `D` changes sign.
+#[derive(Debug, Clone, Default)]
+pub struct Locations {
+    left: Counter<i32>,
+    right: Counter<i32>,
+    /// Number of left IDs minus number of right IDs.
+    len_difference: i64,
+    balance: BalanceTree,
+    similarity: i64,
+}
+
+impl Locations {
+    pub fn new() -> Locations {
+        Locations::default()
+    }
+
+    fn lists(&mut self, side: Side) -> (&mut Counter<i32>, &Counter<i32>) {
+        match side {
+            Side::Left => (&mut self.left, &self.right),
+            Side::Right => (&mut self.right, &self.left),
+        }
+    }
+
+    fn shift(&mut self, side: Side, id: i32, delta: i64) {
+        let delta = match side {
+            Side::Left => delta,
+            Side::Right => -delta,
+        };
+        self.len_difference += delta;
+        self.balance.add_from(id, delta);
+    }
+
+    /// Number of times `id` is in one of the lists.
+    pub fn count(&self, side: Side, id: i32) -> u64 {
+        match side {
+            Side::Left => self.left.get(&id),
+            Side::Right => self.right.get(&id),
+        }
+    }
+
+    pub fn insert(&mut self, side: Side, id: i32) {
+        let (list, other) = self.lists(side);
+        list.add(id, 1);
+        let matches = other.get(&id) as i64;
+        self.similarity += id as i64 * matches;
+        self.shift(side, id, 1);
+    }
+
+    /// Removes one occurrence of `id`, returning whether there was one.
+    pub fn remove(&mut self, side: Side, id: i32) -> bool {
+        let (list, other) = self.lists(side);
+        if list.remove(&id, 1) == 0 {
+            return false;
+        }
+        let matches = other.get(&id) as i64;
+        self.similarity -= id as i64 * matches;
+        self.shift(side, id, -1);
+        true
+    }
+
+    /// Sum of the distances between the smallest left and right IDs, the
+    /// second smallest, and so on. `None` unless both lists are as long.
+    pub fn distance(&self) -> Option<i64> {
+        (self.len_difference == 0).then(|| self.balance.total())
+    }
+
+    /// Sum of each left ID times the number of times it is in the right
+    /// list.
+    pub fn similarity(&self) -> i64 {
+        self.similarity
+    }
+}
+
+const LO: i64 = i32::MIN as i64;
+const HI: i64 = i32::MAX as i64;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Node {
+    /// Indices of the two halves, `None` while the whole range holds the
+    /// same value.
+    children: Option<(usize, usize)>,
+    min: i64,
+    max: i64,
+    /// Sum of the absolute values over the range.
+    abs: i64,
+    /// Amount added to the whole range and not yet to the children.
+    pending: i64,
+}
+
+/// Values over every `i32`, all 0 at first, supporting adding to a suffix
+/// and summing absolute values. Nodes are only split when an update starts
+/// inside them, and merged back once their range holds one value again, so
+/// the tree stays proportional to the number of points where values change.
+#[derive(Debug, Clone)]
+struct BalanceTree {
+    nodes: Vec<Node>,
+    /// Slots of merged nodes, reused before growing `nodes`.
+    free: Vec<usize>,
+}
+
+impl Default for BalanceTree {
+    fn default() -> BalanceTree {
+        BalanceTree {
+            nodes: vec![Node::default()],
+            free: vec![],
+        }
+    }
+}
+
+impl BalanceTree {
+    fn total(&self) -> i64 {
+        self.nodes[0].abs
+    }
+
+    /// Adds `delta` to every value from `from` on.
+    fn add_from(&mut self, from: i32, delta: i64) {
+        self.add(0, LO, HI, from as i64, delta);
+    }
+
+    fn add(&mut self, node: usize, lo: i64, hi: i64, from: i64, delta: i64) {
+        if hi < from {
+            return;
+        }
+        let n = self.nodes[node];
+        // Where no value crosses zero, every absolute value moves the same
+        // way and the sum can be updated without looking further down.
+        let same_sign = n.min == n.max
+            || (n.min >= 0 && n.min + delta >= 0)
+            || (n.max <= 0 && n.max + delta <= 0);
+        if from <= lo && same_sign {
+            self.apply(node, hi - lo + 1, delta);
+            return;
+        }
+
+        let mid = (lo + hi).div_euclid(2);
+        let (left, right) = self.split(node, lo, mid, hi);
+        self.add(left, lo, mid, from, delta);
+        self.add(right, mid + 1, hi, from, delta);
+
+        let (l, r) = (self.nodes[left], self.nodes[right]);
+        let n = &mut self.nodes[node];
+        n.min = l.min.min(r.min);
+        n.max = l.max.max(r.max);
+        n.abs = l.abs + r.abs;
+        if n.min == n.max {
+            // A constant node has no children below it, so both halves are
+            // leaves by now.
+            debug_assert!(l.children.is_none() && r.children.is_none());
+            n.children = None;
+            self.free.extend([left, right]);
+        }
+    }
+
+    /// Adds `delta` to a whole node of `len` values, none of which changes
+    /// sign unless they are all equal.
+    fn apply(&mut self, node: usize, len: i64, delta: i64) {
+        let n = &mut self.nodes[node];
+        if n.min == n.max {
+            n.abs = (n.min + delta).abs() * len;
+        } else if n.min >= 0 {
+            n.abs += delta * len;
+        } else {
+            n.abs -= delta * len;
+        }
+        n.min += delta;
+        n.max += delta;
+        n.pending += delta;
+    }
+
+    fn alloc(&mut self, node: Node) -> usize {
+        match self.free.pop() {
+            Some(i) => {
+                self.nodes[i] = node;
+                i
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /// Children of `node`, creating them or handing them its pending amount.
+    fn split(&mut self, node: usize, lo: i64, mid: i64, hi: i64) -> (usize, usize) {
+        let n = self.nodes[node];
+        match n.children {
+            Some((left, right)) => {
+                if n.pending != 0 {
+                    self.apply(left, mid - lo + 1, n.pending);
+                    self.apply(right, hi - mid, n.pending);
+                    self.nodes[node].pending = 0;
+                }
+                (left, right)
+            }
+            None => {
+                let half = |len: i64| Node {
+                    children: None,
+                    min: n.min,
+                    max: n.min,
+                    abs: n.min.abs() * len,
+                    pending: 0,
+                };
+                let left = self.alloc(half(mid - lo + 1));
+                let right = self.alloc(half(hi - mid));
+                let n = &mut self.nodes[node];
+                n.children = Some((left, right));
+                n.pending = 0;
+                (left, right)
+            }
+        }
+    }
+
+    /// Nodes in use, for checking that merged ones are given back.
+    #[cfg(test)]
+    fn live_nodes(&self) -> usize {
+        self.nodes.len() - self.free.len()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Update {
+    Insert,
+    Remove,
+}
+
+/// Reads `[+|-] left right`, a pair of IDs to insert (the default) or remove.
+fn read_update(line: &Line) -> Result<(Update, i32, i32), ParseError> {
+    let update = alt((
+        value(literal("+"), Update::Insert),
+        value(literal("-"), Update::Remove),
+    ));
+    let ((update, left), right) = parser::parse_line(
+        line,
+        separated_pair(
+            pair(optional(terminated(update, space1())), signed()),
+            space1(),
+            signed(),
+        ),
+    )?;
+    Ok((update.unwrap_or(Update::Insert), left, right))
+}
+
+/// Applies one update line. A removal of an ID that is not there fails
+/// without changing either list.
+fn apply_update(locations: &mut Locations, line: &Line) -> Result<(), ParseError> {
+    let (update, left, right) = read_update(line)?;
+    let pair = [(Side::Left, left), (Side::Right, right)];
+    match update {
+        Update::Insert => {
+            for (side, id) in pair {
+                locations.insert(side, id);
+            }
+        }
+        Update::Remove => {
+            for (side, id) in pair {
+                if locations.count(side, id) == 0 {
+                    let list = match side {
+                        Side::Left => "left",
+                        Side::Right => "right",
+                    };
+                    return Err(line.error_line(format!("{} is not in the {} list", id, list)));
+                }
+            }
+            for (side, id) in pair {
+                locations.remove(side, id);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Applies update lines one by one, writing the distance and similarity
+/// after each. The distance is `-` while the lists differ in length.
+pub fn stream(lines: impl Iterator<Item = Result<Line>>, mut out: impl Write) -> Result<()> {
+    let mut locations = Locations::new();
+    for line in lines {
+        let line = line?;
+        if line.text.trim().is_empty() {
+            continue;
+        }
+        apply_update(&mut locations, &line)?;
+        let distance = match locations.distance() {
+            Some(d) => d.to_string(),
+            None => "-".to_string(),
+        };
+        writeln!(out, "{} {}", distance, locations.similarity())?;
+    }
+    Ok(())
+}
+
+impl Solution for Day01 {
+    type Input = Locations;
+
+    fn parse(reader: Reader) -> Result<Self::Input> {
+        let mut locations = Locations::new();
+        for line in reader.numbered_lines() {
+            let line = line?;
+            if line.text.trim().is_empty() {
+                continue;
+            }
+            let (lhs, rhs) =
+                parser::parse_line(&line, separated_pair(signed(), space1(), signed()))?;
+            locations.insert(Side::Left, lhs);
+            locations.insert(Side::Right, rhs);
+        }
+
+        Ok(locations)
+    }
+
+    fn part1(locations: &Self::Input) -> Result<Answer> {
+        let ans = locations
+            .distance()
+            .context("lists have different lengths")?;
+        Ok(ans.into())
+    }
+
+    fn part2(locations: &Self::Input) -> Result<Answer> {
+        Ok(locations.similarity().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Rng;
+
+    fn line(text: &str) -> Line {
+        Line {
+            path: "<test>".into(),
+            number: 1,
+            text: text.to_string(),
+        }
+    }
+
+    /// Distance by sorting both lists and zipping them.
+    fn reference(left: &[i32], right: &[i32]) -> Option<i64> {
+        if left.len() != right.len() {
+            return None;
+        }
+        let (mut left, mut right) = (left.to_vec(), right.to_vec());
+        left.sort();
+        right.sort();
+        Some(
+            left.iter()
+                .zip(&right)
+                .map(|(&a, &b)| (a as i64 - b as i64).abs())
+                .sum(),
+        )
+    }
+
+    fn similarity(left: &[i32], right: &[i32]) -> i64 {
+        left.iter()
+            .map(|&a| a as i64 * right.iter().filter(|&&b| b == a).count() as i64)
+            .sum()
+    }
+
+    fn check(locations: &Locations, left: &[i32], right: &[i32]) {
+        assert_eq!(locations.distance(), reference(left, right));
+        assert_eq!(locations.similarity(), similarity(left, right));
+    }
+
+    #[test]
+    fn sample() {
+        let mut locations = Locations::new();
+        for (a, b) in [(3, 4), (4, 3), (2, 5), (1, 3), (3, 9), (3, 3)] {
+            locations.insert(Side::Left, a);
+            locations.insert(Side::Right, b);
+        }
+        assert_eq!(locations.distance(), Some(11));
+        assert_eq!(locations.similarity(), 31);
+    }
+
+    #[test]
+    fn interleaved_inserts_shift_every_pair() {
+        let (mut left, mut right) = (vec![], vec![]);
+        let mut locations = Locations::new();
+        for i in 0..200 {
+            locations.insert(Side::Left, 2 * i);
+            locations.insert(Side::Right, 2 * i + 1);
+            left.push(2 * i);
+            right.push(2 * i + 1);
+        }
+        check(&locations, &left, &right);
+        for _ in 0..20 {
+            locations.insert(Side::Left, -5);
+            left.push(-5);
+            check(&locations, &left, &right);
+            locations.insert(Side::Right, -5);
+            right.push(-5);
+            check(&locations, &left, &right);
+        }
+    }
+
+    #[test]
+    fn random_updates_match_reference() {
+        let mut rng = Rng::new(0x2545_f491_4f6c_dd1d);
+        let (mut left, mut right) = (vec![], vec![]);
+        let mut locations = Locations::new();
+        for _ in 0..2000 {
+            let (side, list) = if rng.below(2) == 0 {
+                (Side::Left, &mut left)
+            } else {
+                (Side::Right, &mut right)
+            };
+            if rng.below(3) == 0 && !list.is_empty() {
+                let id = list.swap_remove(rng.below(list.len()));
+                assert!(locations.remove(side, id));
+            } else {
+                let id = rng.below(40) as i32 - 20;
+                list.push(id);
+                locations.insert(side, id);
+            }
+            check(&locations, &left, &right);
+        }
+    }
+
+    #[test]
+    fn removed_ids_give_their_nodes_back() {
+        let mut locations = Locations::new();
+        for round in 0..3 {
+            for i in 0..500 {
+                locations.insert(Side::Left, i * 7919 - 1_000_000);
+                locations.insert(Side::Right, i * 104_729);
+            }
+            let live = locations.balance.live_nodes();
+            assert!(live > 1);
+            for i in 0..500 {
+                assert!(locations.remove(Side::Left, i * 7919 - 1_000_000));
+                assert!(locations.remove(Side::Right, i * 104_729));
+            }
+            assert_eq!(locations.distance(), Some(0));
+            assert_eq!(locations.balance.live_nodes(), 1, "round {}", round);
+        }
+    }
+
+    #[test]
+    fn extreme_ids_do_not_overflow() {
+        let mut locations = Locations::new();
+        locations.insert(Side::Left, i32::MIN);
+        locations.insert(Side::Right, i32::MAX);
+        assert_eq!(locations.distance(), Some(u32::MAX as i64));
+    }
+
+    #[test]
+    fn removing_missing_id() {
+        let mut locations = Locations::new();
+        locations.insert(Side::Left, 7);
+        assert!(!locations.remove(Side::Right, 7));
+        assert!(!locations.remove(Side::Left, 8));
+        assert_eq!(locations.distance(), None);
+        assert!(locations.remove(Side::Left, 7));
+        assert_eq!(locations.distance(), Some(0));
+    }
+
+    #[test]
+    fn stream_prints_after_each_update() {
+        let lines = ["3 4", "+ 4 3", "", "- 3 4", "1 1"].map(|text| Ok(line(text)));
+        let mut out = vec![];
+        stream(lines.into_iter(), &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "1 0\n0 7\n1 0\n1 1\n");
+    }
+
+    #[test]
+    fn failed_removal_changes_nothing() {
+        let mut locations = Locations::new();
+        apply_update(&mut locations, &line("1 2")).unwrap();
+        let err = apply_update(&mut locations, &line("- 1 5")).unwrap_err();
+        assert_eq!(err.message, "5 is not in the right list");
+        assert_eq!(locations.count(Side::Left, 1), 1);
+        assert_eq!(locations.distance(), Some(1));
+
+        let err = apply_update(&mut locations, &line("- 3 2")).unwrap_err();
+        assert_eq!(err.message, "3 is not in the left list");
+        assert_eq!(locations.count(Side::Right, 2), 1);
+    }
+}
@@ -0,0 +1,62 @@
+use anyhow::Result;
+
+use crate::{
+    answer::Answer,
+    input::Reader,
+    parser::{self, separated, signed, space1},
+    solution::Solution,
+};
+
+pub struct Day02;
+
+fn safe(v: &[i32]) -> bool {
+    let is_diff = |v: &[i32]| {
+        let s = (v[0] - v[1]).abs();
+        (1..=3).contains(&s)
+    };
+    let is_incr = |v: &[i32]| v[0] < v[1];
+    let is_decr = |v: &[i32]| v[0] > v[1];
+    v.windows(2).all(is_diff) && (v.windows(2).all(is_incr) || v.windows(2).all(is_decr))
+}
+
+fn safe_rm(v: &[i32]) -> bool {
+    let skip = |v: &[i32], i: usize| -> Vec<i32> {
+        v[..i].iter().chain(v[i + 1..].iter()).cloned().collect()
+    };
+
+    safe(v) || (0..v.len()).map(|i| skip(v, i)).any(|v| safe(&v))
+}
+
+fn run(reports: &[Vec<i32>], f: fn(&[i32]) -> bool) -> Result<Answer> {
+    let safe_cnt: i32 = reports
+        .iter()
+        .map(|levels| if f(levels) { 1 } else { 0 })
+        .sum();
+
+    Ok(safe_cnt.into())
+}
+
+impl Solution for Day02 {
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(reader: Reader) -> Result<Self::Input> {
+        let mut reports = vec![];
+        for line in reader.numbered_lines() {
+            let line = line?;
+            if line.text.trim().is_empty() {
+                continue;
+            }
+            let levels = parser::parse_line(&line, separated(signed(), space1()))?;
+            reports.push(levels);
+        }
+        Ok(reports)
+    }
+
+    fn part1(reports: &Self::Input) -> Result<Answer> {
+        run(reports, safe)
+    }
+
+    fn part2(reports: &Self::Input) -> Result<Answer> {
+        run(reports, safe_rm)
+    }
+}
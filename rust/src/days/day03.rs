@@ -0,0 +1,72 @@
+use std::io;
+
+use anyhow::Result;
+
+use crate::{
+    answer::Answer,
+    input::Reader,
+    parser::{self, alt, delimited, literal, separated_pair, unsigned, value, PResult},
+    solution::Solution,
+};
+
+pub struct Day03;
+
+#[derive(Debug, Clone)]
+pub enum Instruction {
+    Do,
+    Dont,
+    Mul { lhs: i32, rhs: i32 },
+}
+
+fn instruction(input: &str) -> PResult<'_, Instruction> {
+    let mul = parser::map(
+        delimited(
+            literal("mul("),
+            separated_pair(unsigned(), literal(","), unsigned()),
+            literal(")"),
+        ),
+        |(lhs, rhs)| Instruction::Mul { lhs, rhs },
+    );
+    alt((
+        value(literal("do()"), Instruction::Do),
+        value(literal("don't()"), Instruction::Dont),
+        mul,
+    ))(input)
+}
+
+impl Solution for Day03 {
+    type Input = Vec<Instruction>;
+
+    fn parse(reader: Reader) -> Result<Self::Input> {
+        let memory = io::read_to_string(reader)?;
+        Ok(parser::scan(&memory, instruction).collect())
+    }
+
+    fn part1(instructions: &Self::Input) -> Result<Answer> {
+        let sum = instructions
+            .iter()
+            .map(|ins| match ins {
+                Instruction::Mul { lhs, rhs } => lhs * rhs,
+                _ => 0,
+            })
+            .sum::<i32>();
+        Ok(sum.into())
+    }
+
+    fn part2(instructions: &Self::Input) -> Result<Answer> {
+        let mut sum = 0;
+
+        let mut last = &Instruction::Do;
+        for ins in instructions {
+            match ins {
+                ins @ (Instruction::Do | Instruction::Dont) => last = ins,
+                Instruction::Mul { lhs, rhs } => {
+                    if matches!(last, Instruction::Do) {
+                        sum += lhs * rhs
+                    }
+                }
+            }
+        }
+        Ok(sum.into())
+    }
+}
@@ -0,0 +1,76 @@
+use anyhow::Result;
+
+use crate::{
+    answer::Answer,
+    geom::{Direction, Point},
+    grid::{Grid, Pos},
+    input::Reader,
+    solution::Solution,
+};
+
+pub struct Day04;
+
+fn xmas_count(map: &Grid<char>, pos: Pos) -> i32 {
+    let mut cnt = 0;
+
+    for direction in Direction::ALL {
+        let s: String = (0..4)
+            .map_while(|i| map.get(pos + direction.offset() * i))
+            .collect();
+
+        if s == "XMAS" {
+            cnt += 1;
+        }
+    }
+    cnt
+}
+
+fn xmas_3x3_count(map: &Grid<char>, pos: Pos) -> i32 {
+    if !map.contains(pos + Point::new(2, 2)) {
+        return 0;
+    }
+
+    if map[pos + Point::new(1, 1)] != 'A' {
+        return 0;
+    }
+
+    let corners = [
+        Point::new(0, 0),
+        Point::new(0, 2),
+        Point::new(2, 2),
+        Point::new(2, 0),
+        Point::new(0, 0),
+        Point::new(0, 2),
+        Point::new(2, 2),
+        Point::new(2, 0),
+    ];
+    for offset in 0..4 {
+        let mut check = String::new();
+        for &corner in &corners[offset..offset + 4] {
+            check.push(map[pos + corner]);
+        }
+        if check == "MMSS" {
+            return 1;
+        }
+    }
+
+    0
+}
+
+impl Solution for Day04 {
+    type Input = Grid<char>;
+
+    fn parse(reader: Reader) -> Result<Self::Input> {
+        Grid::parse(reader, Some)
+    }
+
+    fn part1(map: &Self::Input) -> Result<Answer> {
+        let cnt: i32 = map.positions().map(|p| xmas_count(map, p)).sum();
+        Ok(cnt.into())
+    }
+
+    fn part2(map: &Self::Input) -> Result<Answer> {
+        let cnt: i32 = map.positions().map(|p| xmas_3x3_count(map, p)).sum();
+        Ok(cnt.into())
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{Context, Result};
+
+use crate::{
+    answer::Answer,
+    error::{Line, ParseError},
+    graph::Graph,
+    input::Reader,
+    parser::{self, literal, separated, separated_pair, unsigned},
+    solution::Solution,
+};
+
+pub struct Day05;
+
+#[derive(Default, Debug)]
+pub struct Rules {
+    graph: Graph<usize>,
+}
+
+#[derive(Debug)]
+pub struct Page {
+    data: Vec<usize>,
+}
+
+impl Rules {
+    fn add_rule(&mut self, x: usize, y: usize) {
+        self.graph.add_edge(x, y);
+    }
+
+    /// Position of each page of `subset` in the order the rules ask for.
+    fn topological_sort(&self, subset: BTreeSet<usize>) -> Result<BTreeMap<usize, usize>> {
+        let sorted = self
+            .graph
+            .topological_sort_subset(&subset)
+            .context("rules are contradictory")?;
+        Ok(sorted
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| (v, i))
+            .collect())
+    }
+}
+
+impl Page {
+    fn parse(line: &Line) -> Result<Page, ParseError> {
+        let data = parser::parse_line(line, separated(unsigned(), literal(",")))?;
+        Ok(Page { data })
+    }
+
+    fn good(&self, rules: &Rules) -> Result<Option<usize>> {
+        let subset: BTreeSet<usize> = self.data.iter().cloned().collect();
+        let sorted = rules.topological_sort(subset)?;
+        let order: Vec<usize> = self.data.iter().map(|v| sorted[v]).collect();
+        if order.windows(2).all(|v| v[0] <= v[1]) {
+            Ok(Some(self.data[self.data.len() / 2]))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn bad(&self, rules: &Rules) -> Result<Option<usize>> {
+        let subset: BTreeSet<usize> = self.data.iter().cloned().collect();
+        let sorted = rules.topological_sort(subset)?;
+        let order: Vec<usize> = self.data.iter().map(|v| sorted[v]).collect();
+        if order.windows(2).all(|v| v[0] <= v[1]) {
+            Ok(None)
+        } else {
+            let mut order = order;
+            order.sort();
+            let mid = order[order.len() / 2];
+            Ok(sorted
+                .iter()
+                .find(|&(_, value)| value == &mid)
+                .map(|(key, _)| *key))
+        }
+    }
+}
+
+impl Solution for Day05 {
+    type Input = (Rules, Vec<Page>);
+
+    fn parse(reader: Reader) -> Result<Self::Input> {
+        let mut lines = reader.numbered_lines();
+
+        let mut rules = Rules::default();
+        for line in lines.by_ref() {
+            let line = line?;
+            if line.text.trim().is_empty() {
+                break;
+            }
+            let (x, y) =
+                parser::parse_line(&line, separated_pair(unsigned(), literal("|"), unsigned()))?;
+            rules.add_rule(x, y);
+        }
+
+        let mut pages = vec![];
+        for line in lines {
+            let line = line?;
+            if line.text.trim().is_empty() {
+                continue;
+            }
+            let page = Page::parse(&line)?;
+            // Pages no rule mentions still need a place in the order.
+            for &v in &page.data {
+                rules.graph.add_node(v);
+            }
+            pages.push(page);
+        }
+
+        Ok((rules, pages))
+    }
+
+    fn part1((rules, pages): &Self::Input) -> Result<Answer> {
+        let mut cnt = 0;
+        for page in pages {
+            cnt += page.good(rules)?.unwrap_or(0);
+        }
+        Ok(cnt.into())
+    }
+
+    fn part2((rules, pages): &Self::Input) -> Result<Answer> {
+        let mut cnt = 0;
+        for page in pages {
+            cnt += page.bad(rules)?.unwrap_or(0);
+        }
+        Ok(cnt.into())
+    }
+}
@@ -0,0 +1,224 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+
+use crate::{
+    answer::Answer,
+    bitgrid::StateSet,
+    geom::{Direction, Point},
+    grid::Grid,
+    input::Reader,
+    solution::Solution,
+};
+
+pub struct Day06;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    pos: Point,
+    direction: Direction,
+}
+
+#[derive(Clone)]
+pub struct Map {
+    data: Grid<char>,
+    horizontal_obstacles: BTreeMap<i32, Vec<i32>>,
+    vertical_obstacles: BTreeMap<i32, Vec<i32>>,
+}
+
+impl Position {
+    fn next_pos(&self) -> Position {
+        Position {
+            pos: self.pos + self.direction,
+            direction: self.direction,
+        }
+    }
+
+    fn is_outside(&self, map: &Map) -> bool {
+        !map.data.contains(self.pos)
+    }
+
+    fn is_valid(&self, map: &Map) -> bool {
+        !self.is_outside(map) && map.data[self.pos] != '#'
+    }
+
+    fn turn(&mut self) {
+        self.direction = self.direction.turn_right();
+    }
+
+    fn farthest_pos(&self, map: &Map) -> Option<Position> {
+        debug_assert!(self.is_valid(map));
+        let Point { x, y } = self.pos;
+        let pos = match self.direction {
+            Direction::Up => {
+                let obstacles = map.vertical_obstacles.get(&y)?;
+                let index = obstacles.binary_search(&x).unwrap_err();
+                let index = index.checked_sub(1)?;
+                Point::new(obstacles[index] + 1, y)
+            }
+            Direction::Down => {
+                let obstacles = map.vertical_obstacles.get(&y)?;
+                let index = obstacles.binary_search(&x).unwrap_err();
+                Point::new(obstacles.get(index)? - 1, y)
+            }
+            Direction::Left => {
+                let obstacles = map.horizontal_obstacles.get(&x)?;
+                let index = obstacles.binary_search(&y).unwrap_err();
+                let index = index.checked_sub(1)?;
+                Point::new(x, obstacles[index] + 1)
+            }
+            Direction::Right => {
+                let obstacles = map.horizontal_obstacles.get(&x)?;
+                let index = obstacles.binary_search(&y).unwrap_err();
+                Point::new(x, obstacles.get(index)? - 1)
+            }
+            _ => unreachable!("the guard only moves orthogonally"),
+        };
+        Some(Position {
+            pos,
+            direction: self.direction,
+        })
+    }
+}
+
+impl Map {
+    fn new(data: Grid<char>) -> Map {
+        let mut horizontal_obstacles: BTreeMap<i32, Vec<i32>> = BTreeMap::new();
+        let mut vertical_obstacles: BTreeMap<i32, Vec<i32>> = BTreeMap::new();
+        for Point { x, y } in data.find_all(&'#') {
+            vertical_obstacles.entry(y).or_default().push(x);
+            horizontal_obstacles.entry(x).or_default().push(y);
+        }
+
+        Map {
+            data,
+            horizontal_obstacles,
+            vertical_obstacles,
+        }
+    }
+    /// Where the guard is after one more move, `None` once it leaves.
+    fn step(&self, pos: &Position) -> Option<Position> {
+        let next_pos = pos.next_pos();
+        if next_pos.is_outside(self) {
+            None
+        } else if next_pos.is_valid(self) {
+            Some(next_pos)
+        } else {
+            let mut pos = *pos;
+            pos.turn();
+            Some(pos)
+        }
+    }
+
+    fn states(&self) -> StateSet {
+        StateSet::new(self.data.height(), self.data.width())
+    }
+
+    /// Cells the guard walks over, `None` if it never leaves.
+    fn run(&self, start: &Position) -> Option<Vec<Point>> {
+        if !start.is_valid(self) {
+            return None;
+        }
+
+        // Coming back to a cell facing the same way means going round forever.
+        let mut seen = self.states();
+        seen.insert((start.pos, start.direction));
+        let mut pos = *start;
+        while let Some(next) = self.step(&pos) {
+            if !seen.insert((next.pos, next.direction)) {
+                return None;
+            }
+            pos = next;
+        }
+        Some(seen.cells().iter().collect())
+    }
+
+    fn mark_obstacle(&mut self, pos: Point) {
+        debug_assert!(self.data[pos] != '#');
+        self.data[pos] = '#';
+        let Point { x, y } = pos;
+
+        let vertical_obstacles = self.vertical_obstacles.entry(y).or_default();
+        let index = vertical_obstacles.partition_point(|&v| v <= x);
+        vertical_obstacles.insert(index, x);
+
+        let horizontal_obstacles = self.horizontal_obstacles.entry(x).or_default();
+        let index = horizontal_obstacles.partition_point(|&v| v <= y);
+        horizontal_obstacles.insert(index, y);
+    }
+    fn unmark_obstacle(&mut self, pos: Point) {
+        debug_assert!(self.data[pos] == '#');
+        self.data[pos] = '.';
+        let Point { x, y } = pos;
+
+        let pos = self.vertical_obstacles[&y]
+            .iter()
+            .position(|v| v == &x)
+            .expect("position must exist");
+        self.vertical_obstacles.get_mut(&y).unwrap().remove(pos);
+
+        let pos = self.horizontal_obstacles[&x]
+            .iter()
+            .position(|v| v == &y)
+            .expect("position must exist");
+        self.horizontal_obstacles.get_mut(&x).unwrap().remove(pos);
+    }
+
+    /// Whether the guard walks in circles, jumping from obstacle to
+    /// obstacle. `seen` is cleared and reused across calls.
+    fn is_loop(&self, start: &Position, seen: &mut StateSet) -> bool {
+        seen.clear();
+        let mut pos = *start;
+        while seen.insert((pos.pos, pos.direction)) {
+            let Some(next) = pos.farthest_pos(self) else {
+                return false;
+            };
+            pos = next;
+            pos.turn();
+        }
+        true
+    }
+}
+
+impl Solution for Day06 {
+    type Input = (Map, Position);
+
+    fn parse(reader: Reader) -> Result<Self::Input> {
+        let data = Grid::parse(reader, Some)?;
+        let pos = data.find(&'^').context("guard `^` not found")?;
+        let start = Position {
+            pos,
+            direction: Direction::Up,
+        };
+
+        let map = Map::new(data);
+
+        Ok((map, start))
+    }
+
+    fn part1((map, start): &Self::Input) -> Result<Answer> {
+        let positions = map.run(start).context("guard never leaves the map")?;
+        Ok(positions.len().into())
+    }
+
+    fn part2((map, start): &Self::Input) -> Result<Answer> {
+        let mut map = map.clone();
+        let positions = map.run(start).context("guard never leaves the map")?;
+
+        let mut seen = map.states();
+        let mut ans = 0;
+        for pos in positions {
+            if pos == start.pos {
+                continue;
+            }
+
+            map.mark_obstacle(pos);
+            if map.is_loop(start, &mut seen) {
+                ans += 1;
+            }
+            map.unmark_obstacle(pos);
+        }
+
+        Ok(ans.into())
+    }
+}
@@ -0,0 +1,116 @@
+use anyhow::Result;
+
+use crate::{
+    answer::Answer,
+    combinatorics::Sequences,
+    error::{Line, ParseError},
+    input::Reader,
+    numtheory,
+    parser::{self, literal, preceded, separated, separated_pair, space0, space1, unsigned},
+    solution::Solution,
+};
+
+pub struct Day07;
+
+#[derive(Clone, Copy)]
+enum Op {
+    Add,
+    Mul,
+    Concat,
+}
+
+impl Op {
+    fn apply(self, acc: u64, value: u64) -> Option<u64> {
+        match self {
+            Op::Add => acc.checked_add(value),
+            Op::Mul => acc.checked_mul(value),
+            Op::Concat => numtheory::concat_digits(acc, value),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Equation {
+    lhs: u64,
+    rhs: Vec<u64>,
+}
+
+impl Equation {
+    fn parse(line: &Line) -> Result<Equation, ParseError> {
+        let (lhs, rhs) = parser::parse_line(
+            line,
+            separated_pair(
+                unsigned(),
+                literal(":"),
+                preceded(space0(), separated(unsigned(), space1())),
+            ),
+        )?;
+        Ok(Equation { lhs, rhs })
+    }
+
+    /// Whether some choice of `ops` between the numbers gives the test
+    /// value.
+    fn solvable(&self, ops: &[Op]) -> bool {
+        // With no zero, no operator makes the value smaller, so a prefix
+        // that already overshoots can be skipped altogether.
+        let growing = self.rhs.iter().all(|&v| v > 0);
+
+        let mut choices = Sequences::uniform(ops.len(), self.rhs.len() - 1);
+        while let Some(choice) = choices.next() {
+            let mut acc = Some(self.rhs[0]);
+            let mut failed_at = None;
+            for (i, (&value, &op)) in self.rhs[1..].iter().zip(choice).enumerate() {
+                acc = acc.and_then(|acc| ops[op].apply(acc, value));
+                match acc {
+                    Some(acc) if !growing || acc <= self.lhs => {}
+                    _ => {
+                        failed_at = Some(i + 1);
+                        break;
+                    }
+                }
+            }
+            match failed_at {
+                Some(len) => choices.prune(len),
+                None if acc == Some(self.lhs) => return true,
+                None => {}
+            }
+        }
+        false
+    }
+}
+
+impl Solution for Day07 {
+    type Input = Vec<Equation>;
+
+    fn parse(reader: Reader) -> Result<Self::Input> {
+        let mut equations = vec![];
+        for line in reader.numbered_lines() {
+            let line = line?;
+            if line.text.trim().is_empty() {
+                continue;
+            }
+            equations.push(Equation::parse(&line)?);
+        }
+        Ok(equations)
+    }
+
+    fn part1(equations: &Self::Input) -> Result<Answer> {
+        let sum = equations
+            .iter()
+            .filter(|equation| equation.solvable(&[Op::Add, Op::Mul]))
+            .map(|equation| equation.lhs)
+            .sum::<u64>();
+
+        Ok(sum.into())
+    }
+
+    fn part2(equations: &Self::Input) -> Result<Answer> {
+        let sum = equations
+            .iter()
+            .filter(|equation| equation.solvable(&[Op::Add, Op::Mul, Op::Concat]))
+            .map(|equation| equation.lhs)
+            .sum::<u64>();
+
+        Ok(sum.into())
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::BTreeSet;
+
+use anyhow::Result;
+
+use crate::{
+    answer::Answer,
+    combinatorics,
+    grid::{Grid, Pos},
+    input::Reader,
+    solution::Solution,
+};
+
+pub struct Day08;
+
+#[derive(Debug)]
+pub struct Map {
+    data: Grid<char>,
+}
+
+impl Map {
+    fn read(reader: Reader) -> Result<Map> {
+        Ok(Map {
+            data: Grid::parse(reader, Some)?,
+        })
+    }
+
+    fn unique_atenna(&self) -> Vec<char> {
+        let mut unique_chars = BTreeSet::new();
+        for (_, c) in self.data.iter() {
+            if c != &'.' {
+                unique_chars.insert(*c);
+            }
+        }
+        unique_chars.into_iter().collect()
+    }
+
+    fn _pairwise_positions(&self, antenna: &char) -> Vec<(Pos, Pos)> {
+        let locations: Vec<Pos> = self.data.find_all(antenna).collect();
+        combinatorics::product(&locations, &locations)
+            .map(|(&a, &b)| (a, b))
+            .collect()
+    }
+
+    fn pairwise_positions(&self) -> Vec<(Pos, Pos)> {
+        self.unique_atenna()
+            .iter()
+            .flat_map(|c| self._pairwise_positions(c))
+            .collect()
+    }
+
+    fn all_positions(&self) -> Vec<Pos> {
+        self.data.positions().collect()
+    }
+}
+
+fn run(map: &Map, good_position: fn(&(Pos, Pos), &Pos) -> bool) -> Result<Answer> {
+    let pairwise_positions = map.pairwise_positions();
+    let cnt = map
+        .all_positions()
+        .iter()
+        .filter(|p| pairwise_positions.iter().any(|ps| good_position(ps, p)))
+        .count();
+    Ok(cnt.into())
+}
+
+impl Solution for Day08 {
+    type Input = Map;
+
+    fn parse(reader: Reader) -> Result<Self::Input> {
+        Map::read(reader)
+    }
+
+    fn part1(map: &Self::Input) -> Result<Answer> {
+        run(map, |(p1, p2): &(Pos, Pos), p: &Pos| -> bool {
+            p1 != p2 && (*p + *p1 == *p2 * 2 || *p + *p2 == *p1 * 2)
+        })
+    }
+
+    fn part2(map: &Self::Input) -> Result<Answer> {
+        run(map, |(p1, p2): &(Pos, Pos), p: &Pos| -> bool {
+            if p1 == p2 {
+                false
+            } else if p == p1 || p == p2 {
+                true
+            } else {
+                let diff = *p - *p1;
+                let d = *p1 - *p2;
+                diff.x % d.x == 0 && diff.y % d.y == 0 && diff.x / d.x == diff.y / d.y
+            }
+        })
+    }
+}
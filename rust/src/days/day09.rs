@@ -0,0 +1,107 @@
+use std::collections::BinaryHeap;
+
+use anyhow::Result;
+
+use crate::{
+    answer::Answer,
+    input::Reader,
+    interval::IntervalAllocator,
+    parser::{self, digit, many0},
+    solution::Solution,
+};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<usize>;
+
+    fn parse(reader: Reader) -> Result<Self::Input> {
+        let mut digits = vec![];
+        for line in reader.numbered_lines() {
+            let line = line?;
+            let line_digits = parser::parse_line(&line, many0(digit()))?;
+            digits.extend(line_digits.into_iter().map(|d| d as usize));
+        }
+        Ok(digits)
+    }
+
+    fn part1(disk: &Self::Input) -> Result<Answer> {
+        let mut location = 0;
+        let mut nums = vec![];
+        let mut dots = vec![];
+        for (i, &n) in disk.iter().enumerate() {
+            if n == 0 {
+                continue;
+            }
+            if i % 2 == 0 {
+                for _ in 0..n {
+                    nums.push((location, i / 2));
+                    location += 1;
+                }
+            } else {
+                dots.extend(location..location + n);
+                location += n;
+            }
+        }
+
+        let mut dots = BinaryHeap::from_iter(dots.into_iter().map(|d| -(d as i32)));
+        let mut moved_nums = vec![];
+
+        while let Some((loc_num, num)) = nums.pop() {
+            dots.push(-(loc_num as i32));
+
+            let dot_loc = dots.pop().unwrap();
+            let dot_loc = (-dot_loc) as usize;
+            moved_nums.push((dot_loc, num));
+            if loc_num == dot_loc {
+                break;
+            }
+        }
+
+        let ans = moved_nums
+            .iter()
+            .chain(nums.iter())
+            .map(|(loc, num)| loc * num)
+            .sum::<usize>();
+
+        Ok(ans.into())
+    }
+
+    fn part2(disk: &Self::Input) -> Result<Answer> {
+        let mut location = 0;
+        let mut nums = vec![];
+        let mut dots = vec![];
+        for (i, &n) in disk.iter().enumerate() {
+            if n == 0 {
+                continue;
+            }
+            if i % 2 == 0 {
+                nums.push((i / 2, n, location));
+            } else {
+                dots.push((location, n));
+            }
+            location += n;
+        }
+
+        let mut free = IntervalAllocator::new(location);
+        for (dot_loc, dot_size) in dots {
+            free.free(dot_loc, dot_size);
+        }
+
+        let calculate = |num, size, loc_from| {
+            let acc = loc_from * size + (size - 1) * size / 2;
+            acc * num
+        };
+
+        let mut ans = 0;
+
+        for (num, num_size, num_loc_from) in nums.into_iter().rev() {
+            let loc = free
+                .allocate_first_fit_before(num_size, num_loc_from)
+                .unwrap_or(num_loc_from);
+            ans += calculate(num, num_size, loc);
+        }
+
+        Ok(ans.into())
+    }
+}
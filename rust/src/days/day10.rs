@@ -0,0 +1,76 @@
+use anyhow::Result;
+
+use crate::{
+    answer::Answer,
+    bitgrid::BitGrid,
+    grid::{Grid, Pos},
+    input::Reader,
+    search,
+    solution::Solution,
+};
+
+pub struct Day10;
+
+#[derive(Debug)]
+pub struct Map {
+    data: Grid<u8>,
+}
+
+impl Map {
+    fn zeros(&self) -> Vec<Pos> {
+        self.data.find_all(&0).collect()
+    }
+
+    /// Cells one step higher than `pos`.
+    fn uphill(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        let value = self.data[pos];
+        self.data
+            .neighbours4(pos)
+            .filter(move |&p| self.data[p] == value + 1)
+    }
+
+    /// Number of 9s reachable from `pos`. `seen` is cleared and reused
+    /// across calls.
+    fn count_score(&self, pos: Pos, seen: &mut BitGrid) -> usize {
+        seen.clear();
+        search::flood([pos], |&p| self.uphill(p), seen);
+        seen.iter().filter(|&p| self.data[p] == 9).count()
+    }
+
+    /// Every trail climbs one step at a time, so all of them are shortest
+    /// paths and the rating is the number of those.
+    fn count_score_part2(&self, pos: Pos) -> u64 {
+        let paths = search::bfs([pos], |&p| self.uphill(p));
+        paths
+            .path_counts()
+            .into_iter()
+            .filter(|&(p, _)| self.data[p] == 9)
+            .map(|(_, count)| count)
+            .sum()
+    }
+}
+
+impl Solution for Day10 {
+    type Input = Map;
+
+    fn parse(reader: Reader) -> Result<Self::Input> {
+        let data = Grid::parse(reader, |c| c.to_digit(10).map(|d| d as u8))?;
+        Ok(Map { data })
+    }
+
+    fn part1(map: &Self::Input) -> Result<Answer> {
+        let mut seen = BitGrid::new(map.data.height(), map.data.width());
+        let zeros = map.zeros();
+        let ans: usize = zeros
+            .into_iter()
+            .map(|p| map.count_score(p, &mut seen))
+            .sum();
+        Ok(ans.into())
+    }
+
+    fn part2(map: &Self::Input) -> Result<Answer> {
+        let zeros = map.zeros();
+        let ans: u64 = zeros.into_iter().map(|p| map.count_score_part2(p)).sum();
+        Ok(ans.into())
+    }
+}
@@ -0,0 +1,75 @@
+use anyhow::{Context, Result};
+
+use crate::{
+    answer::Answer,
+    counter::Counter,
+    input::Reader,
+    numtheory,
+    parser::{self, separated, space1, unsigned},
+    solution::Solution,
+};
+
+pub struct Day11;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Stone {
+    Zero,
+    Odd { n: u64 },
+    Even { n: u64, digits: u32 },
+}
+
+impl From<u64> for Stone {
+    fn from(n: u64) -> Self {
+        if n == 0 {
+            Stone::Zero
+        } else {
+            let digits = numtheory::digit_count(n);
+            if digits % 2 == 1 {
+                Stone::Odd { n }
+            } else {
+                Stone::Even { n, digits }
+            }
+        }
+    }
+}
+
+impl Stone {
+    fn divide(&self) -> Vec<Stone> {
+        match self {
+            Stone::Zero => vec![Stone::Odd { n: 1 }],
+            Stone::Odd { n } => {
+                vec![Stone::from(n * 2024)]
+            }
+            Stone::Even { n, digits } => {
+                let pow = numtheory::pow10(digits / 2);
+                vec![Stone::from(n / pow), Stone::from(n % pow)]
+            }
+        }
+    }
+}
+
+fn run(stones: &[Stone], times: u8) -> Result<Answer> {
+    let mut counts: Counter<Stone> = stones.iter().cloned().collect();
+    for _ in 0..times {
+        counts = counts.step(|stone| stone.divide().into_iter().map(|s| (s, 1)));
+    }
+    Ok(counts.total().into())
+}
+
+impl Solution for Day11 {
+    type Input = Vec<Stone>;
+
+    fn parse(reader: Reader) -> Result<Self::Input> {
+        let line = reader.numbered_lines().next().context("empty input")??;
+        let stones = parser::parse_line(&line, separated(unsigned::<u64>(), space1()))?;
+        Ok(stones.into_iter().map(Stone::from).collect())
+    }
+
+    fn part1(stones: &Self::Input) -> Result<Answer> {
+        run(stones, 25)
+    }
+
+    fn part2(stones: &Self::Input) -> Result<Answer> {
+        run(stones, 75)
+    }
+}
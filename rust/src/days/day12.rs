@@ -0,0 +1,45 @@
+use anyhow::Result;
+
+use crate::{
+    answer::Answer,
+    grid::Grid,
+    input::Reader,
+    region::{self, Region},
+    solution::Solution,
+};
+
+pub struct Day12;
+
+#[derive(Debug)]
+pub struct Map {
+    data: Grid<char>,
+}
+
+impl Map {
+    /// Sum over every region of its area times `fence`.
+    fn prices(&self, fence: impl Fn(&Region) -> usize) -> usize {
+        region::analyse(&self.data, |a, b| a == b)
+            .regions
+            .iter()
+            .map(|r| r.area * fence(r))
+            .sum()
+    }
+}
+
+impl Solution for Day12 {
+    type Input = Map;
+
+    fn parse(reader: Reader) -> Result<Self::Input> {
+        Ok(Map {
+            data: Grid::parse(reader, Some)?,
+        })
+    }
+
+    fn part1(map: &Self::Input) -> Result<Answer> {
+        Ok(map.prices(|r| r.perimeter).into())
+    }
+
+    fn part2(map: &Self::Input) -> Result<Answer> {
+        Ok(map.prices(|r| r.sides).into())
+    }
+}
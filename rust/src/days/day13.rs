@@ -0,0 +1,141 @@
+use anyhow::Result;
+
+use crate::{
+    answer::Answer,
+    error::{Line, ParseError},
+    input::Reader,
+    linalg::{self, Rational},
+    parser::{self, literal, pair, preceded, separated_pair, signed, space0},
+    solution::Solution,
+};
+
+pub struct Day13;
+
+/// A claw machine: presses of buttons A and B must add up to the prize.
+#[derive(Debug)]
+pub struct Machine {
+    a: (i64, i64),
+    b: (i64, i64),
+    prize: (i64, i64),
+}
+
+impl Machine {
+    /// Fewest tokens to win the prize, pressing each button at most `limit`
+    /// times. A press of A costs 3 tokens, one of B costs 1.
+    fn cheapest(&self, limit: i128) -> Option<i128> {
+        let matrix = [vec![self.a.0, self.b.0], vec![self.a.1, self.b.1]];
+        let cost = |presses: &[i128]| 3 * presses[0] + presses[1];
+        match linalg::solve_integer(&matrix, &[self.prize.0, self.prize.1]) {
+            linalg::Solution::None => None,
+            linalg::Solution::Unique(presses) => presses
+                .iter()
+                .all(|n| (0..=limit).contains(n))
+                .then(|| cost(&presses)),
+            linalg::Solution::Parametric { particular, basis } => match &basis[..] {
+                // Both buttons move along the same line: the cost is linear
+                // in `t`, so it is cheapest at one end of the allowed range.
+                [direction] => {
+                    let (lo, hi) = press_range(&particular, direction, limit)?;
+                    let at = |t: i128| -> Vec<i128> {
+                        particular
+                            .iter()
+                            .zip(direction)
+                            .map(|(p, d)| p + t * d)
+                            .collect()
+                    };
+                    Some(cost(&at(lo)).min(cost(&at(hi))))
+                }
+                // Neither button moves the claw, and the prize is where it is.
+                _ => Some(0),
+            },
+        }
+    }
+}
+
+/// Range of `t` keeping every count of `particular + t * direction` within
+/// `0..=limit`.
+fn press_range(particular: &[i128], direction: &[i128], limit: i128) -> Option<(i128, i128)> {
+    let (mut lo, mut hi) = (i128::MIN, i128::MAX);
+    for (&p, &d) in particular.iter().zip(direction) {
+        if d == 0 {
+            if !(0..=limit).contains(&p) {
+                return None;
+            }
+            continue;
+        }
+        let from = Rational::new(-p, d);
+        let to = Rational::new(limit - p, d);
+        let (from, to) = if d > 0 { (from, to) } else { (to, from) };
+        lo = lo.max(from.ceil());
+        hi = hi.min(to.floor());
+    }
+    (lo <= hi).then_some((lo, hi))
+}
+
+/// Reads `{label} X{sep}a, Y{sep}b` into `(a, b)`.
+fn read_pair(line: &Line, label: &str, sep: &str) -> Result<(i64, i64), ParseError> {
+    parser::parse_line(
+        line,
+        preceded(
+            pair(literal(label), space0()),
+            separated_pair(
+                preceded(pair(literal("X"), literal(sep)), signed()),
+                pair(literal(","), space0()),
+                preceded(pair(literal("Y"), literal(sep)), signed()),
+            ),
+        ),
+    )
+}
+
+fn read_machine(lines: &[Line]) -> Result<Machine, ParseError> {
+    let [line1, line2, line3] = lines else {
+        let last = lines.last().expect("chunks are never empty");
+        return Err(last.error_line("expected button A, button B and prize lines"));
+    };
+
+    Ok(Machine {
+        a: read_pair(line1, "Button A:", "+")?,
+        b: read_pair(line2, "Button B:", "+")?,
+        prize: read_pair(line3, "Prize:", "=")?,
+    })
+}
+
+impl Solution for Day13 {
+    type Input = Vec<Machine>;
+
+    fn parse(reader: Reader) -> Result<Self::Input> {
+        let mut lines = vec![];
+        for line in reader.numbered_lines() {
+            let line = line?;
+            if !line.text.trim().is_empty() {
+                lines.push(line);
+            }
+        }
+
+        let machines = lines
+            .chunks(3)
+            .map(read_machine)
+            .collect::<Result<_, _>>()?;
+        Ok(machines)
+    }
+
+    fn part1(machines: &Self::Input) -> Result<Answer> {
+        let ans: i128 = machines.iter().filter_map(|m| m.cheapest(100)).sum();
+        Ok(ans.into())
+    }
+
+    fn part2(machines: &Self::Input) -> Result<Answer> {
+        let ans: i128 = machines
+            .iter()
+            .filter_map(|m| {
+                let (x, y) = m.prize;
+                let far = Machine {
+                    prize: (x + 10000000000000, y + 10000000000000),
+                    ..*m
+                };
+                far.cheapest(i64::MAX as i128)
+            })
+            .sum();
+        Ok(ans.into())
+    }
+}
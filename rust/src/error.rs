@@ -0,0 +1,131 @@
+use std::{fmt::Display, rc::Rc, str::FromStr};
+
+/// Malformed puzzle input, pointing at the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Name of the input, a file path or `<stdin>`/`<memory>`.
+    pub path: String,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, in characters.
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}: `{}`",
+            self.path, self.line, self.column, self.message, self.text
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A line of input together with where it came from, used to build
+/// `ParseError`s pointing into it.
+#[derive(Debug, Clone)]
+pub struct Line {
+    pub path: Rc<str>,
+    /// 1-based line number.
+    pub number: usize,
+    pub text: String,
+}
+
+impl Line {
+    /// Error at a byte offset of the line. An offset inside a character
+    /// points at that character.
+    pub fn error_at(&self, offset: usize, text: &str, message: impl Display) -> ParseError {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        ParseError {
+            path: self.path.to_string(),
+            line: self.number,
+            column: self.text[..offset].chars().count() + 1,
+            text: text.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Error about `token`, which must be a slice of `self.text`.
+    pub fn error(&self, token: &str, message: impl Display) -> ParseError {
+        self.error_at(self.offset(token), token, message)
+    }
+
+    /// Error about the line as a whole.
+    pub fn error_line(&self, message: impl Display) -> ParseError {
+        self.error_at(0, &self.text, message)
+    }
+
+    /// Byte offset of `token` in the line, or the end of the line if `token`
+    /// is not a slice of it.
+    pub fn offset(&self, token: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let pos = token.as_ptr() as usize;
+        if pos >= start && pos + token.len() <= start + self.text.len() {
+            pos - start
+        } else {
+            self.text.len()
+        }
+    }
+
+    /// Parses `token`, which must be a slice of `self.text`.
+    pub fn parse<T>(&self, token: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        token.trim().parse::<T>().map_err(|e| self.error(token, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(text: &str) -> Line {
+        Line {
+            path: "day01/input.txt".into(),
+            number: 4,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn columns_count_characters() {
+        let l = line("été 12x");
+        let err = l.error(&l.text[6..], "bad number");
+        assert_eq!((err.line, err.column), (4, 5));
+        assert_eq!(err.to_string(), "day01/input.txt:4:5: bad number: `12x`");
+    }
+
+    #[test]
+    fn offsets_inside_a_character_do_not_panic() {
+        let l = line("aé€b");
+        assert_eq!(l.error_at(2, "é", "x").column, 2);
+        assert_eq!(l.error_at(4, "€", "x").column, 3);
+        assert_eq!(l.error_at(5, "€", "x").column, 3);
+        assert_eq!(l.error_at(100, "", "x").column, 5);
+    }
+
+    #[test]
+    fn tokens_outside_the_line_point_at_its_end() {
+        let l = line("abc");
+        assert_eq!(l.offset(&l.text[1..2]), 1);
+        assert_eq!(l.offset("b"), 3);
+        assert_eq!(l.error_line("whole").column, 1);
+    }
+
+    #[test]
+    fn parse_reports_the_token() {
+        let l = line("7 x");
+        assert_eq!(l.parse::<u8>(&l.text[..1]), Ok(7));
+        let err = l.parse::<u8>(&l.text[2..]).unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (3, "x"));
+    }
+}
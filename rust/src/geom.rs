@@ -0,0 +1,297 @@
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+/// A 2D point or vector. Grid days use `x` for the row and `y` for the
+/// column, so `Direction::Up` decreases `x`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T = i32> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Point<T> {
+        Point { x, y }
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Point<T> {
+        Point { x, y }
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(p: Point<T>) -> (T, T) {
+        (p.x, p.y)
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Point<T>;
+
+    fn add(self, rhs: Point<T>) -> Point<T> {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Point<T>;
+
+    fn sub(self, rhs: Point<T>) -> Point<T> {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
+    type Output = Point<T>;
+
+    fn mul(self, rhs: T) -> Point<T> {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Point<T>;
+
+    fn neg(self) -> Point<T> {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Point<T>) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, rhs: Point<T>) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+macro_rules! impl_signed {
+    ($($t:ty),*) => {$(
+        impl Point<$t> {
+            pub const ORIGIN: Point<$t> = Point::new(0, 0);
+
+            pub fn manhattan(self, other: Point<$t>) -> $t {
+                (self.x - other.x).abs() + (self.y - other.y).abs()
+            }
+
+            pub fn chebyshev(self, other: Point<$t>) -> $t {
+                (self.x - other.x).abs().max((self.y - other.y).abs())
+            }
+
+            /// Rotates a quarter turn clockwise around the origin, as seen
+            /// on a grid printed with rows going down.
+            pub fn rotate_right(self) -> Point<$t> {
+                Point::new(self.y, -self.x)
+            }
+
+            pub fn rotate_left(self) -> Point<$t> {
+                Point::new(-self.y, self.x)
+            }
+
+            /// The point one step away in `direction`.
+            pub fn step(self, direction: Direction) -> Point<$t> {
+                let (dx, dy) = direction.delta();
+                Point::new(self.x + dx as $t, self.y + dy as $t)
+            }
+        }
+
+        impl Add<Direction> for Point<$t> {
+            type Output = Point<$t>;
+
+            fn add(self, rhs: Direction) -> Point<$t> {
+                self.step(rhs)
+            }
+        }
+    )*};
+}
+
+impl_signed!(i32, i64);
+
+/// One of the 8 compass directions, in clockwise order from `Up`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// The 4 orthogonal directions, clockwise from `Up`.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::UpRight,
+        Direction::DownRight,
+        Direction::DownLeft,
+        Direction::UpLeft,
+    ];
+
+    /// All 8 directions, clockwise from `Up`.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    fn from_index(i: usize) -> Direction {
+        Direction::ALL[i % 8]
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// `(row, column)` offset of one step.
+    pub const fn delta(self) -> (i32, i32) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::UpRight => (-1, 1),
+            Direction::Right => (0, 1),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (1, 0),
+            Direction::DownLeft => (1, -1),
+            Direction::Left => (0, -1),
+            Direction::UpLeft => (-1, -1),
+        }
+    }
+
+    pub const fn offset(self) -> Point {
+        let (x, y) = self.delta();
+        Point::new(x, y)
+    }
+
+    pub fn is_cardinal(self) -> bool {
+        self.index().is_multiple_of(2)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        !self.is_cardinal()
+    }
+
+    /// A quarter turn clockwise.
+    pub fn turn_right(self) -> Direction {
+        Direction::from_index(self.index() + 2)
+    }
+
+    /// A quarter turn counter-clockwise.
+    pub fn turn_left(self) -> Direction {
+        Direction::from_index(self.index() + 6)
+    }
+
+    /// An eighth of a turn clockwise.
+    pub fn turn_right45(self) -> Direction {
+        Direction::from_index(self.index() + 1)
+    }
+
+    /// An eighth of a turn counter-clockwise.
+    pub fn turn_left45(self) -> Direction {
+        Direction::from_index(self.index() + 7)
+    }
+
+    pub fn reverse(self) -> Direction {
+        Direction::from_index(self.index() + 4)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_arithmetic() {
+        let a: Point = Point::new(3, -2);
+        let b = Point::new(-1, 5);
+        assert_eq!(a + b, Point::new(2, 3));
+        assert_eq!(a - b, Point::new(4, -7));
+        assert_eq!(a * 3, Point::new(9, -6));
+        assert_eq!(-a, Point::new(-3, 2));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+
+        assert_eq!(a.manhattan(b), 11);
+        assert_eq!(a.chebyshev(b), 7);
+        assert_eq!(Point::<i64>::ORIGIN.manhattan(Point::new(-4, 4)), 8);
+        assert_eq!(Point::from((1, 2)), Point::new(1, 2));
+        assert_eq!(<(i32, i32)>::from(a), (3, -2));
+        assert_eq!(a.to_string(), "(3, -2)");
+    }
+
+    #[test]
+    fn steps_follow_rows_and_columns() {
+        let p: Point = Point::new(5, 5);
+        assert_eq!(p + Direction::Up, Point::new(4, 5));
+        assert_eq!(p + Direction::Right, Point::new(5, 6));
+        assert_eq!(p + Direction::DownLeft, Point::new(6, 4));
+        assert_eq!(
+            Point::<i64>::new(0, 0).step(Direction::UpLeft),
+            Point::new(-1, -1)
+        );
+        for d in Direction::ALL {
+            assert_eq!(p + d + d.reverse(), p);
+            assert_eq!(Point::<i32>::ORIGIN + d, d.offset());
+        }
+    }
+
+    #[test]
+    fn turns() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::UpLeft.turn_right45(), Direction::Up);
+        assert_eq!(Direction::Up.turn_left45(), Direction::UpLeft);
+        assert_eq!(Direction::DownRight.reverse(), Direction::UpLeft);
+
+        for d in Direction::ALL {
+            assert_eq!(d.turn_right().turn_left(), d);
+            assert_eq!(d.turn_right45().turn_right45(), d.turn_right());
+            assert_eq!(d.turn_left().turn_left(), d.reverse());
+            assert_eq!(d.is_cardinal(), Direction::CARDINAL.contains(&d));
+            assert_eq!(d.is_diagonal(), Direction::DIAGONAL.contains(&d));
+        }
+    }
+
+    #[test]
+    fn rotations_match_turns() {
+        for d in Direction::ALL {
+            assert_eq!(d.offset().rotate_right(), d.turn_right().offset());
+            assert_eq!(d.offset().rotate_left(), d.turn_left().offset());
+        }
+        let p: Point = Point::new(2, 7);
+        assert_eq!(p.rotate_right().rotate_right(), -p);
+        assert_eq!(p.rotate_right().rotate_left(), p);
+    }
+}
@@ -0,0 +1,415 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    fmt::{Debug, Display},
+};
+
+/// A directed graph stored as adjacency lists. Nodes are kept in insertion
+/// order, which is also the order algorithms visit them in, so results are
+/// deterministic.
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    nodes: Vec<N>,
+    index: BTreeMap<N, usize>,
+    adj: Vec<BTreeSet<usize>>,
+}
+
+/// A cycle found while sorting, as the nodes along it: each one has an edge
+/// to the next and the last one has an edge back to the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<N> {
+    pub nodes: Vec<N>,
+}
+
+impl<N: Display> Display for Cycle<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cycle ")?;
+        for node in &self.nodes {
+            write!(f, "{} -> ", node)?;
+        }
+        match self.nodes.first() {
+            Some(first) => write!(f, "{}", first),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<N: Debug + Display> std::error::Error for Cycle<N> {}
+
+impl<N> Default for Graph<N> {
+    fn default() -> Self {
+        Graph {
+            nodes: vec![],
+            index: BTreeMap::new(),
+            adj: vec![],
+        }
+    }
+}
+
+impl<N: Ord + Clone> Graph<N> {
+    pub fn new() -> Graph<N> {
+        Graph::default()
+    }
+
+    /// Adds `node` if it is not in the graph yet and returns its index.
+    pub fn add_node(&mut self, node: N) -> usize {
+        if let Some(&i) = self.index.get(&node) {
+            return i;
+        }
+        let i = self.nodes.len();
+        self.index.insert(node.clone(), i);
+        self.nodes.push(node);
+        self.adj.push(BTreeSet::new());
+        i
+    }
+
+    pub fn add_edge(&mut self, from: N, to: N) {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+        self.adj[from].insert(to);
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.index.contains_key(node)
+    }
+
+    pub fn has_edge(&self, from: &N, to: &N) -> bool {
+        match (self.index.get(from), self.index.get(to)) {
+            (Some(&from), Some(to)) => self.adj[from].contains(to),
+            _ => false,
+        }
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.nodes.iter()
+    }
+
+    pub fn successors<'a>(&'a self, node: &N) -> impl Iterator<Item = &'a N> + 'a {
+        let adj = self.index.get(node).map(|&i| &self.adj[i]);
+        adj.into_iter().flatten().map(|&i| &self.nodes[i])
+    }
+
+    /// The graph induced by the nodes of `subset` that are in this graph.
+    pub fn subgraph(&self, subset: &BTreeSet<N>) -> Graph<N> {
+        let mut graph = Graph::new();
+        let keep = self.mask(subset);
+        for (i, node) in self.nodes.iter().enumerate() {
+            if keep[i] {
+                graph.add_node(node.clone());
+            }
+        }
+        for (i, adj) in self.adj.iter().enumerate() {
+            for &j in adj {
+                if keep[i] && keep[j] {
+                    graph.add_edge(self.nodes[i].clone(), self.nodes[j].clone());
+                }
+            }
+        }
+        graph
+    }
+
+    /// Sorts the nodes so that every edge goes forward, using Kahn's
+    /// algorithm. Ties are broken by insertion order.
+    pub fn topological_sort(&self) -> Result<Vec<N>, Cycle<N>> {
+        self.kahn(&vec![true; self.len()])
+    }
+
+    /// Like `topological_sort`, ignoring nodes outside of `subset` and their
+    /// edges.
+    pub fn topological_sort_subset(&self, subset: &BTreeSet<N>) -> Result<Vec<N>, Cycle<N>> {
+        self.kahn(&self.mask(subset))
+    }
+
+    /// Topological sort by depth-first search, without recursion.
+    pub fn topological_sort_dfs(&self) -> Result<Vec<N>, Cycle<N>> {
+        self.dfs(&vec![true; self.len()])
+    }
+
+    pub fn topological_sort_dfs_subset(&self, subset: &BTreeSet<N>) -> Result<Vec<N>, Cycle<N>> {
+        self.dfs(&self.mask(subset))
+    }
+
+    /// Strongly connected components, in topological order of the
+    /// condensation: edges between components only go forward.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<N>> {
+        const UNVISITED: usize = usize::MAX;
+
+        let n = self.len();
+        let mut order = vec![UNVISITED; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = vec![];
+        let mut components = vec![];
+        let mut counter = 0;
+
+        for root in 0..n {
+            if order[root] != UNVISITED {
+                continue;
+            }
+            // (node, successors not looked at yet)
+            let mut work: Vec<(usize, Vec<usize>)> = vec![];
+            let mut next = Some(root);
+            loop {
+                if let Some(v) = next.take() {
+                    order[v] = counter;
+                    low[v] = counter;
+                    counter += 1;
+                    stack.push(v);
+                    on_stack[v] = true;
+                    work.push((v, self.adj[v].iter().rev().copied().collect()));
+                }
+                let Some((v, pending)) = work.last_mut() else {
+                    break;
+                };
+                let v = *v;
+                if let Some(u) = pending.pop() {
+                    if order[u] == UNVISITED {
+                        next = Some(u);
+                    } else if on_stack[u] {
+                        low[v] = low[v].min(order[u]);
+                    }
+                    continue;
+                }
+
+                work.pop();
+                if let Some(&(parent, _)) = work.last() {
+                    low[parent] = low[parent].min(low[v]);
+                }
+                if low[v] == order[v] {
+                    let mut component = vec![];
+                    while let Some(u) = stack.pop() {
+                        on_stack[u] = false;
+                        component.push(self.nodes[u].clone());
+                        if u == v {
+                            break;
+                        }
+                    }
+                    component.reverse();
+                    components.push(component);
+                }
+            }
+        }
+
+        // Tarjan's algorithm finds sinks first.
+        components.reverse();
+        components
+    }
+
+    fn mask(&self, subset: &BTreeSet<N>) -> Vec<bool> {
+        self.nodes.iter().map(|n| subset.contains(n)).collect()
+    }
+
+    fn kahn(&self, keep: &[bool]) -> Result<Vec<N>, Cycle<N>> {
+        let mut indegree = vec![0; self.len()];
+        for (i, adj) in self.adj.iter().enumerate() {
+            if keep[i] {
+                for &j in adj.iter().filter(|&&j| keep[j]) {
+                    indegree[j] += 1;
+                }
+            }
+        }
+
+        let mut queue: VecDeque<usize> = (0..self.len())
+            .filter(|&i| keep[i] && indegree[i] == 0)
+            .collect();
+        let mut sorted = vec![];
+        while let Some(i) = queue.pop_front() {
+            sorted.push(i);
+            for &j in self.adj[i].iter().filter(|&&j| keep[j]) {
+                indegree[j] -= 1;
+                if indegree[j] == 0 {
+                    queue.push_back(j);
+                }
+            }
+        }
+
+        if sorted.len() == keep.iter().filter(|&&k| k).count() {
+            return Ok(sorted.into_iter().map(|i| self.nodes[i].clone()).collect());
+        }
+
+        // Every node left has a predecessor that is left too, so walking
+        // predecessors from any of them has to come back around.
+        let left: Vec<bool> = (0..self.len())
+            .map(|i| keep[i] && indegree[i] > 0)
+            .collect();
+        let mut predecessor = vec![usize::MAX; self.len()];
+        for (i, adj) in self.adj.iter().enumerate() {
+            if left[i] {
+                for &j in adj.iter().filter(|&&j| left[j]) {
+                    predecessor[j] = i;
+                }
+            }
+        }
+
+        let start = left.iter().position(|&l| l).expect("a node is left");
+        let mut seen = vec![false; self.len()];
+        let mut v = start;
+        while !seen[v] {
+            seen[v] = true;
+            v = predecessor[v];
+        }
+        let mut cycle = vec![v];
+        let mut u = predecessor[v];
+        while u != v {
+            cycle.push(u);
+            u = predecessor[u];
+        }
+        cycle.reverse();
+        Err(self.cycle(cycle))
+    }
+
+    fn dfs(&self, keep: &[bool]) -> Result<Vec<N>, Cycle<N>> {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum State {
+            New,
+            Active,
+            Done,
+        }
+
+        let mut state = vec![State::New; self.len()];
+        let mut sorted = vec![];
+
+        for root in (0..self.len()).filter(|&i| keep[i]) {
+            if state[root] != State::New {
+                continue;
+            }
+            let mut path: Vec<(usize, Vec<usize>)> = vec![];
+            state[root] = State::Active;
+            path.push((root, self.adj[root].iter().rev().copied().collect()));
+
+            while let Some((v, pending)) = path.last_mut() {
+                let v = *v;
+                match pending.pop() {
+                    Some(u) if !keep[u] => {}
+                    Some(u) => match state[u] {
+                        State::New => {
+                            state[u] = State::Active;
+                            path.push((u, self.adj[u].iter().rev().copied().collect()));
+                        }
+                        State::Active => {
+                            let from = path.iter().position(|&(w, _)| w == u).unwrap();
+                            let cycle = path[from..].iter().map(|&(w, _)| w).collect();
+                            return Err(self.cycle(cycle));
+                        }
+                        State::Done => {}
+                    },
+                    None => {
+                        state[v] = State::Done;
+                        sorted.push(v);
+                        path.pop();
+                    }
+                }
+            }
+        }
+
+        sorted.reverse();
+        Ok(sorted.into_iter().map(|i| self.nodes[i].clone()).collect())
+    }
+
+    fn cycle(&self, indices: Vec<usize>) -> Cycle<N> {
+        Cycle {
+            nodes: indices.into_iter().map(|i| self.nodes[i].clone()).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(u32, u32)]) -> Graph<u32> {
+        let mut graph = Graph::new();
+        for &(from, to) in edges {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    fn assert_sorted(graph: &Graph<u32>, order: &[u32]) {
+        let position = |n: &u32| order.iter().position(|m| m == n);
+        for from in order {
+            for to in graph.successors(from) {
+                if let Some(to) = position(to) {
+                    assert!(position(from) < Some(to), "{} before {}", to, from);
+                }
+            }
+        }
+    }
+
+    fn assert_cycle(graph: &Graph<u32>, cycle: &Cycle<u32>) {
+        let nodes = &cycle.nodes;
+        assert!(!nodes.is_empty());
+        for (i, from) in nodes.iter().enumerate() {
+            let to = &nodes[(i + 1) % nodes.len()];
+            assert!(graph.has_edge(from, to), "no edge {} -> {}", from, to);
+        }
+    }
+
+    #[test]
+    fn sorts_break_ties_by_insertion_order() {
+        let g = graph(&[(5, 1), (3, 1), (1, 4), (3, 4)]);
+        assert_eq!(g.topological_sort(), Ok(vec![5, 3, 1, 4]));
+        let dfs = g.topological_sort_dfs().unwrap();
+        assert_eq!(dfs.len(), 4);
+        assert_sorted(&g, &dfs);
+    }
+
+    #[test]
+    fn sorts_report_cycles() {
+        // 1 -> 2 -> 3 -> 4 -> 2, with 1 and 5 hanging off it.
+        let g = graph(&[(1, 2), (2, 3), (3, 4), (4, 2), (4, 5)]);
+        for result in [g.topological_sort(), g.topological_sort_dfs()] {
+            let cycle = result.unwrap_err();
+            assert_cycle(&g, &cycle);
+            assert_eq!(cycle.nodes.len(), 3);
+        }
+    }
+
+    #[test]
+    fn self_loop_is_a_cycle() {
+        let g = graph(&[(1, 2), (2, 2)]);
+        for result in [g.topological_sort(), g.topological_sort_dfs()] {
+            assert_eq!(result, Err(Cycle { nodes: vec![2] }));
+        }
+        assert_eq!(Cycle { nodes: vec![2] }.to_string(), "cycle 2 -> 2");
+    }
+
+    #[test]
+    fn subset_leaves_out_cycles() {
+        let g = graph(&[(1, 2), (2, 3), (3, 1), (3, 4), (4, 5)]);
+        let subset: BTreeSet<u32> = [2, 3, 4, 5, 9].into();
+        assert_eq!(g.topological_sort_subset(&subset), Ok(vec![2, 3, 4, 5]));
+        assert_eq!(g.topological_sort_dfs_subset(&subset), Ok(vec![2, 3, 4, 5]));
+
+        let sub = g.subgraph(&subset);
+        assert_eq!(sub.len(), 4);
+        assert!(!sub.contains(&1));
+        assert!(!sub.has_edge(&3, &1));
+        assert_eq!(sub.topological_sort(), Ok(vec![2, 3, 4, 5]));
+    }
+
+    #[test]
+    fn strongly_connected_components_in_topological_order() {
+        let g = graph(&[(1, 2), (2, 1), (2, 3), (3, 4), (4, 5), (5, 3), (6, 5)]);
+        assert_eq!(
+            g.strongly_connected_components(),
+            vec![vec![6], vec![1, 2], vec![3, 4, 5]]
+        );
+    }
+
+    #[test]
+    fn empty_graph() {
+        let g: Graph<u32> = Graph::new();
+        assert!(g.is_empty());
+        assert_eq!(g.topological_sort(), Ok(vec![]));
+        assert_eq!(g.topological_sort_dfs(), Ok(vec![]));
+        assert!(g.strongly_connected_components().is_empty());
+    }
+}
@@ -0,0 +1,326 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+    rc::Rc,
+    str::FromStr,
+};
+
+use anyhow::{bail, Result};
+
+use crate::{
+    error::Line,
+    geom::{Direction, Point},
+    input::Reader,
+};
+
+/// Cell coordinates, `x` being the row and `y` the column. Signed so that
+/// stepping off the edge can be expressed and checked with `Grid::contains`.
+pub type Pos = Point;
+
+/// A rectangular grid stored row-major in a single `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    height: usize,
+    width: usize,
+    data: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(height: usize, width: usize, data: Vec<T>) -> Grid<T> {
+        assert_eq!(height * width, data.len(), "grid size mismatch");
+        Grid {
+            height,
+            width,
+            data,
+        }
+    }
+
+    pub fn filled(height: usize, width: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::new(height, width, vec![value; height * width])
+    }
+
+    /// Parses one cell per character, skipping blank lines. Rows must all be
+    /// as wide as the first one.
+    pub fn parse(reader: Reader, cell: impl FnMut(char) -> Option<T>) -> Result<Grid<T>> {
+        Grid::parse_lines(reader.numbered_lines(), cell)
+    }
+
+    pub fn parse_lines(
+        lines: impl Iterator<Item = Result<Line>>,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>> {
+        let mut height = 0;
+        let mut width = 0;
+        let mut data = vec![];
+
+        for line in lines {
+            let line = line?;
+            let text = line.text.trim_end();
+            if text.is_empty() {
+                continue;
+            }
+
+            let mut row_width = 0;
+            for (offset, c) in text.char_indices() {
+                match cell(c) {
+                    Some(value) => data.push(value),
+                    None => {
+                        let c = &text[offset..offset + c.len_utf8()];
+                        return Err(line.error_at(offset, c, "unexpected character").into());
+                    }
+                }
+                row_width += 1;
+            }
+
+            if height == 0 {
+                width = row_width;
+            } else if row_width != width {
+                return Err(line
+                    .error_line(format!("row is {} wide, expected {}", row_width, width))
+                    .into());
+            }
+            height += 1;
+        }
+
+        if height == 0 {
+            bail!("empty grid");
+        }
+        Ok(Grid::new(height, width, data))
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn contains(&self, Point { x, y }: Pos) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.height && (y as usize) < self.width
+    }
+
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        if self.contains(pos) {
+            Some(pos.x as usize * self.width + pos.y as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.data[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.data[i])
+    }
+
+    pub fn row(&self, x: usize) -> &[T] {
+        &self.data[x * self.width..(x + 1) * self.width]
+    }
+
+    pub fn column(&self, y: usize) -> impl Iterator<Item = &T> {
+        assert!(y < self.width, "column out of range");
+        self.data.iter().skip(y).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.data.chunks(self.width)
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |x| (0..width).map(move |y| Point::new(x as i32, y as i32)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.data.iter())
+    }
+
+    /// In-bounds orthogonal neighbours of `pos`.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.towards(pos, &Direction::CARDINAL)
+    }
+
+    /// In-bounds orthogonal and diagonal neighbours of `pos`.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.towards(pos, &Direction::ALL)
+    }
+
+    fn towards<'a>(
+        &'a self,
+        pos: Pos,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = Pos> + 'a {
+        directions
+            .iter()
+            .map(move |&d| pos + d)
+            .filter(|&p| self.contains(p))
+    }
+
+    /// Positions of every cell equal to `value`, row by row.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Pos> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, v)| *v == value)
+            .map(|(p, _)| p)
+    }
+
+    pub fn find(&self, value: &T) -> Option<Pos>
+    where
+        T: PartialEq,
+    {
+        self.find_all(value).next()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.height, self.width, self.data.iter().map(f).collect())
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", pos))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", pos))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let path: Rc<str> = "<memory>".into();
+        let lines = s.lines().enumerate().map(|(i, text)| {
+            Ok(Line {
+                path: Rc::clone(&path),
+                number: i + 1,
+                text: text.to_string(),
+            })
+        });
+        Grid::parse_lines(lines, Some)
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ParseError;
+
+    fn sample() -> Grid<char> {
+        "abc\ndef\n".parse().unwrap()
+    }
+
+    #[test]
+    fn parses_rows_and_skips_blank_lines() {
+        let grid: Grid<char> = "\nab  \n\ncd\n".parse().unwrap();
+        assert_eq!((grid.height(), grid.width()), (2, 2));
+        assert_eq!(grid.row(1), ['c', 'd']);
+        assert_eq!(grid.to_string(), "ab\ncd\n");
+    }
+
+    #[test]
+    fn rejects_ragged_and_empty_grids() {
+        let err = "abc\nde".parse::<Grid<char>>().unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.message, "row is 2 wide, expected 3");
+
+        assert!("\n  \n".parse::<Grid<char>>().is_err());
+    }
+
+    #[test]
+    fn unexpected_cells_point_at_the_character() {
+        let lines = ["12", "3é"].iter().enumerate().map(|(i, text)| {
+            Ok(Line {
+                path: "digits".into(),
+                number: i + 1,
+                text: text.to_string(),
+            })
+        });
+        let err = Grid::parse_lines(lines, |c| c.to_digit(10)).unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "é"));
+    }
+
+    #[test]
+    fn bounds_checks() {
+        let grid = sample();
+        assert!(grid.contains(Point::new(1, 2)));
+        for outside in [
+            Point::new(-1, 0),
+            Point::new(0, -1),
+            Point::new(2, 0),
+            Point::new(0, 3),
+        ] {
+            assert!(!grid.contains(outside));
+            assert_eq!(grid.get(outside), None);
+        }
+        assert_eq!(grid.get(Point::new(1, 2)), Some(&'f'));
+        assert_eq!(grid[Point::new(0, 1)], 'b');
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the grid")]
+    fn indexing_outside_panics() {
+        let _ = sample()[Point::new(0, 3)];
+    }
+
+    #[test]
+    fn neighbours_stay_inside() {
+        let grid = Grid::filled(3, 3, 0);
+        let corner: Vec<_> = grid.neighbours4(Point::new(0, 0)).collect();
+        assert_eq!(corner.len(), 2);
+        assert!(corner.contains(&Point::new(0, 1)) && corner.contains(&Point::new(1, 0)));
+        assert_eq!(grid.neighbours8(Point::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbours4(Point::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8(Point::new(2, 1)).count(), 5);
+    }
+
+    #[test]
+    fn rows_columns_and_search() {
+        let mut grid = sample();
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.positions().nth(4), Some(Point::new(1, 1)));
+
+        grid[Point::new(1, 0)] = 'a';
+        assert_eq!(grid.find(&'a'), Some(Point::new(0, 0)));
+        assert_eq!(
+            grid.find_all(&'a').collect::<Vec<_>>(),
+            [Point::new(0, 0), Point::new(1, 0)]
+        );
+        assert_eq!(grid.find(&'z'), None);
+
+        let upper = grid.map(|c| c.to_ascii_uppercase());
+        assert_eq!(upper.to_string(), "ABC\nAEF\n");
+    }
+}
@@ -0,0 +1,342 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{bail, Context, Result};
+
+use crate::{
+    bench::{DayBench, Stats, Step},
+    input::InputKind,
+};
+
+/// Default location of the benchmark history, inside cargo's `target/`.
+pub fn default_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("bench-history.tsv")
+}
+
+/// Short hash of `HEAD`, suffixed with `-dirty` when the tree has changes.
+pub fn git_commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .output()
+            .ok()
+            .filter(|out| out.status.success())
+            .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+    };
+
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(commit) => match git(&["status", "--porcelain"]) {
+            Some(status) if !status.is_empty() => format!("{}-dirty", commit),
+            _ => commit,
+        },
+        None => "unknown".to_string(),
+    }
+}
+
+/// One line of the history: the timing of a step in a recorded run.
+#[derive(Debug, Clone)]
+pub struct Record {
+    /// Milliseconds since the epoch at which the run was saved.
+    pub run: u128,
+    pub commit: String,
+    pub day: u16,
+    pub kind: InputKind,
+    pub step: Step,
+    pub stats: Stats,
+}
+
+impl Record {
+    fn key(&self) -> (u16, InputKind, Step) {
+        (self.day, self.kind, self.step)
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.run,
+            self.commit,
+            self.day,
+            self.kind,
+            self.step,
+            self.stats.runs,
+            self.stats.min.as_nanos(),
+            self.stats.median.as_nanos(),
+            self.stats.p95.as_nanos(),
+        )
+    }
+
+    fn from_line(line: &str) -> Result<Record> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 9 {
+            bail!("expected 9 fields, got {}", fields.len());
+        }
+        let nanos = |s: &str| -> Result<Duration> { Ok(Duration::from_nanos(s.parse()?)) };
+        let step = match fields[4] {
+            "parse" => Step::Parse,
+            s => match s.strip_prefix("part").map(str::parse) {
+                Some(Ok(part)) => Step::Part(part),
+                _ => bail!("invalid step `{}`", s),
+            },
+        };
+
+        Ok(Record {
+            run: fields[0].parse()?,
+            commit: fields[1].to_string(),
+            day: fields[2].parse()?,
+            kind: fields[3].parse()?,
+            step,
+            stats: Stats {
+                runs: fields[5].parse()?,
+                min: nanos(fields[6])?,
+                median: nanos(fields[7])?,
+                p95: nanos(fields[8])?,
+            },
+        })
+    }
+}
+
+/// Benchmark results of every saved run, oldest first.
+#[derive(Debug, Default)]
+pub struct History {
+    pub records: Vec<Record>,
+}
+
+impl History {
+    /// Loads the history, treating a missing file as an empty one.
+    pub fn load(path: &Path) -> Result<History> {
+        if !path.is_file() {
+            return Ok(History::default());
+        }
+        let records = fs::read_to_string(path)?
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(lineno, line)| {
+                Record::from_line(line)
+                    .with_context(|| format!("{}:{}", path.display(), lineno + 1))
+            })
+            .collect::<Result<_>>()?;
+        Ok(History { records })
+    }
+
+    /// Appends a run to the history file and returns its records.
+    pub fn append(
+        path: &Path,
+        commit: &str,
+        kind: InputKind,
+        benches: &[DayBench],
+    ) -> Result<Vec<Record>> {
+        let run = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+        let records: Vec<Record> = benches
+            .iter()
+            .flat_map(|bench| {
+                bench.steps.iter().map(|&(step, stats)| Record {
+                    run,
+                    commit: commit.to_string(),
+                    day: bench.day,
+                    kind,
+                    step,
+                    stats,
+                })
+            })
+            .collect();
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut f = OpenOptions::new().create(true).append(true).open(path)?;
+        for record in &records {
+            writeln!(f, "{}", record.to_line())?;
+        }
+        Ok(records)
+    }
+
+    /// Run ids in the order they were saved.
+    pub fn runs(&self) -> Vec<u128> {
+        let mut runs: Vec<u128> = self.records.iter().map(|r| r.run).collect();
+        runs.dedup();
+        runs
+    }
+
+    pub fn run(&self, run: u128) -> Vec<&Record> {
+        self.records.iter().filter(|r| r.run == run).collect()
+    }
+
+    /// Latest run older than `before` recorded at a commit whose hash starts
+    /// with `commit`.
+    pub fn latest_at(&self, commit: &str, before: u128) -> Option<u128> {
+        self.records
+            .iter()
+            .rev()
+            .find(|r| r.run < before && r.commit.starts_with(commit))
+            .map(|r| r.run)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub day: u16,
+    pub kind: InputKind,
+    pub step: Step,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Comparison {
+    /// Relative change of the median, in percent.
+    pub fn change(&self) -> f64 {
+        if self.baseline.is_zero() {
+            return 0.0;
+        }
+        (self.current.as_secs_f64() / self.baseline.as_secs_f64() - 1.0) * 100.0
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change() > threshold
+    }
+}
+
+/// Pairs up the steps measured in both runs by their medians.
+pub fn compare(baseline: &[&Record], current: &[&Record]) -> Vec<Comparison> {
+    current
+        .iter()
+        .filter_map(|cur| {
+            let base = baseline.iter().find(|b| b.key() == cur.key())?;
+            Some(Comparison {
+                day: cur.day,
+                kind: cur.kind,
+                step: cur.step,
+                baseline: base.stats.median,
+                current: cur.stats.median,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(run: u128, commit: &str, day: u16, step: Step, median_ns: u64) -> Record {
+        let median = Duration::from_nanos(median_ns);
+        Record {
+            run,
+            commit: commit.to_string(),
+            day,
+            kind: InputKind::Real,
+            step,
+            stats: Stats {
+                runs: 10,
+                min: median / 2,
+                median,
+                p95: median * 2,
+            },
+        }
+    }
+
+    fn history() -> History {
+        History {
+            records: vec![
+                record(100, "abc1234", 1, Step::Parse, 50),
+                record(100, "abc1234", 1, Step::Part(1), 1000),
+                record(200, "def5678-dirty", 1, Step::Part(1), 1200),
+                record(300, "abc1234", 1, Step::Part(1), 900),
+                record(300, "abc1234", 2, Step::Part(2), 10),
+            ],
+        }
+    }
+
+    #[test]
+    fn records_round_trip() {
+        for r in history().records {
+            let line = r.to_line();
+            assert_eq!(Record::from_line(&line).unwrap().to_line(), line);
+        }
+        let r = record(7, "abc1234", 3, Step::Part(2), 1500);
+        assert_eq!(
+            r.to_line(),
+            "7\tabc1234\t3\tinput\tpart2\t10\t750\t1500\t3000"
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_records() {
+        let line = record(7, "c", 3, Step::Parse, 1).to_line();
+        assert!(Record::from_line(&line.replace("parse", "part")).is_err());
+        assert!(Record::from_line(&line.replace("parse", "step1")).is_err());
+        assert!(Record::from_line(&line.replace("input", "sample0")).is_err());
+        let err = Record::from_line("1\t2\t3").unwrap_err().to_string();
+        assert_eq!(err, "expected 9 fields, got 3");
+    }
+
+    #[test]
+    fn looks_runs_up_by_commit() {
+        let history = history();
+        assert_eq!(history.runs(), [100, 200, 300]);
+        assert_eq!(history.run(300).len(), 2);
+        assert!(history.run(150).is_empty());
+
+        assert_eq!(history.latest_at("abc", u128::MAX), Some(300));
+        assert_eq!(history.latest_at("abc", 300), Some(100));
+        assert_eq!(history.latest_at("def5678", u128::MAX), Some(200));
+        assert_eq!(history.latest_at("abc", 100), None);
+        assert_eq!(history.latest_at("fff", u128::MAX), None);
+    }
+
+    #[test]
+    fn compares_matching_steps() {
+        let history = history();
+        let comparisons = compare(&history.run(100), &history.run(300));
+        assert_eq!(comparisons.len(), 1);
+        let c = &comparisons[0];
+        assert_eq!((c.day, c.step), (1, Step::Part(1)));
+        assert!((c.change() + 10.0).abs() < 1e-9);
+        assert!(!c.is_regression(5.0));
+
+        let comparisons = compare(&history.run(100), &history.run(200));
+        assert!(comparisons[0].is_regression(5.0));
+        assert!(!comparisons[0].is_regression(25.0));
+    }
+
+    #[test]
+    fn appends_and_loads() {
+        let path = std::env::temp_dir()
+            .join(format!("aoc-history-{}", std::process::id()))
+            .join("bench-history.tsv");
+        let _ = fs::remove_file(&path);
+        assert!(History::load(&path).unwrap().records.is_empty());
+
+        let bench = DayBench {
+            day: 4,
+            steps: vec![
+                (Step::Parse, record(0, "", 4, Step::Parse, 20).stats),
+                (Step::Part(1), record(0, "", 4, Step::Part(1), 30).stats),
+            ],
+        };
+        let benches = [bench];
+        let first = History::append(&path, "abc1234", InputKind::Sample(2), &benches).unwrap();
+        History::append(&path, "abc1234", InputKind::Sample(2), &benches).unwrap();
+
+        let loaded = History::load(&path).unwrap();
+        assert_eq!(loaded.records.len(), 4);
+        assert_eq!(loaded.records[0].to_line(), first[0].to_line());
+        assert_eq!(loaded.records[1].kind, InputKind::Sample(2));
+
+        fs::write(&path, "\n1\t2\n").unwrap();
+        let err = format!("{:#}", History::load(&path).unwrap_err());
+        assert!(
+            err.contains("bench-history.tsv:2: expected 9 fields"),
+            "{}",
+            err
+        );
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
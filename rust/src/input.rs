@@ -0,0 +1,411 @@
+use std::{
+    env::{self, current_dir},
+    ffi::OsString,
+    fmt::Display,
+    fs::File,
+    io::{self, BufRead, BufReader, Cursor, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    rc::Rc,
+    str::FromStr,
+};
+
+use anyhow::{bail, Context, Result};
+
+use crate::error::Line;
+
+/// Environment variable pointing at the `input` folder.
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+
+pub trait ReadSeek: Read + Seek {}
+
+impl<T: Read + Seek> ReadSeek for T {}
+
+/// Puzzle input handed to `Solution::parse`, remembering its name so parse
+/// errors can point at it.
+pub struct Reader {
+    path: Rc<str>,
+    inner: BufReader<Box<dyn ReadSeek>>,
+}
+
+impl Reader {
+    pub fn new(path: &str, inner: Box<dyn ReadSeek>) -> Reader {
+        Reader {
+            path: path.into(),
+            inner: BufReader::new(inner),
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Lines of the input numbered from 1, for building `ParseError`s.
+    pub fn numbered_lines(self) -> impl Iterator<Item = Result<Line>> {
+        let path = self.path.clone();
+        self.inner.lines().enumerate().map(move |(i, text)| {
+            Ok(Line {
+                path: path.clone(),
+                number: i + 1,
+                text: text?,
+            })
+        })
+    }
+}
+
+impl Read for Reader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl BufRead for Reader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt)
+    }
+}
+
+impl Seek for Reader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.inner.seek(pos)
+    }
+
+    fn stream_position(&mut self) -> io::Result<u64> {
+        self.inner.stream_position()
+    }
+
+    fn seek_relative(&mut self, offset: i64) -> io::Result<()> {
+        self.inner.seek_relative(offset)
+    }
+}
+
+/// Which of a day's input files to use: the real puzzle input or one of the
+/// samples, `sample.txt` being sample 1 and `sampleN.txt` sample N.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum InputKind {
+    #[default]
+    Real,
+    Sample(u8),
+}
+
+impl InputKind {
+    pub fn is_sample(&self) -> bool {
+        matches!(self, InputKind::Sample(_))
+    }
+
+    pub fn filename(&self) -> String {
+        format!("{}.txt", self)
+    }
+}
+
+impl Display for InputKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputKind::Real => f.pad("input"),
+            InputKind::Sample(1) => f.pad("sample"),
+            InputKind::Sample(n) => f.pad(&format!("sample{}", n)),
+        }
+    }
+}
+
+impl FromStr for InputKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "input" | "real" => Ok(InputKind::Real),
+            "sample" => Ok(InputKind::Sample(1)),
+            _ => match s.strip_prefix("sample").map(str::parse::<u8>) {
+                Some(Ok(n)) if n > 0 => Ok(InputKind::Sample(n)),
+                _ => bail!("invalid input kind `{}`, expected input or sampleN", s),
+            },
+        }
+    }
+}
+
+/// Where the puzzle input of a day comes from.
+#[derive(Debug, Clone, Default)]
+pub enum InputSource {
+    /// `$AOC_INPUT_DIR` if set, otherwise the first `input/` folder found
+    /// walking up from the current directory.
+    #[default]
+    Search,
+    /// An `input/` folder laid out as `dayNN/{input,sample,sampleN}.txt`.
+    Dir(PathBuf),
+    /// A single file, regardless of the day.
+    File(PathBuf),
+    Stdin,
+    Memory(String),
+}
+
+fn day_dir(input_folder: &Path, day: u16) -> PathBuf {
+    input_folder.join(format!("day{:0>2}", day))
+}
+
+fn day_file(input_folder: &Path, day: u16, kind: InputKind) -> PathBuf {
+    day_dir(input_folder, day).join(kind.filename())
+}
+
+fn open_file(path: &Path) -> Result<Reader> {
+    let f = File::open(path).with_context(|| format!("cannot open {}", path.display()))?;
+    Ok(Reader::new(&path.display().to_string(), Box::new(f)))
+}
+
+/// `InputSource::Search` given the value of `$AOC_INPUT_DIR` and the
+/// folder to start walking up from.
+fn search_file(
+    env_dir: Option<OsString>,
+    start: &Path,
+    day: u16,
+    kind: InputKind,
+) -> Result<PathBuf> {
+    if let Some(dir) = env_dir {
+        return InputSource::Dir(dir.into()).resolve(day, kind);
+    }
+
+    let mut tried = vec![];
+    for dir in start.ancestors() {
+        let file = day_file(&dir.join("input"), day, kind);
+        if file.is_file() {
+            return Ok(file);
+        }
+        tried.push(file);
+    }
+
+    let tried: Vec<String> = tried.iter().map(|p| format!("  {}", p.display())).collect();
+    bail!(
+        "{} for day {} not found (set {} to override), tried:\n{}",
+        kind,
+        day,
+        INPUT_DIR_ENV,
+        tried.join("\n")
+    )
+}
+
+fn search_day_dir(env_dir: Option<OsString>, start: &Path, day: u16) -> Result<PathBuf> {
+    if let Some(dir) = env_dir {
+        return InputSource::Dir(dir.into()).day_dir(day);
+    }
+    for dir in start.ancestors() {
+        let input = dir.join("input");
+        if input.is_dir() {
+            return Ok(day_dir(&input, day));
+        }
+    }
+    bail!("no input folder found (set {} to override)", INPUT_DIR_ENV)
+}
+
+impl InputSource {
+    /// Resolves the path of a day's input without opening it.
+    pub fn resolve(&self, day: u16, kind: InputKind) -> Result<PathBuf> {
+        match self {
+            InputSource::Search => {
+                search_file(env::var_os(INPUT_DIR_ENV), &current_dir()?, day, kind)
+            }
+            InputSource::Dir(dir) => {
+                let file = day_file(dir, day, kind);
+                if !file.is_file() {
+                    bail!(
+                        "{} for day {} not found, tried:\n  {}",
+                        kind,
+                        day,
+                        file.display()
+                    )
+                }
+                Ok(file)
+            }
+            InputSource::File(file) => Ok(file.clone()),
+            InputSource::Stdin => bail!("stdin input has no path"),
+            InputSource::Memory(_) => bail!("in-memory input has no path"),
+        }
+    }
+
+    /// Folder holding a day's files, whether or not any of them exist.
+    pub fn day_dir(&self, day: u16) -> Result<PathBuf> {
+        match self {
+            InputSource::Search => search_day_dir(env::var_os(INPUT_DIR_ENV), &current_dir()?, day),
+            InputSource::Dir(dir) => Ok(day_dir(dir, day)),
+            InputSource::File(_) => bail!("a single input file has no day folder"),
+            InputSource::Stdin => bail!("stdin input has no day folder"),
+            InputSource::Memory(_) => bail!("in-memory input has no day folder"),
+        }
+    }
+
+    pub fn open(&self, day: u16, kind: InputKind) -> Result<Reader> {
+        match self {
+            InputSource::Stdin => {
+                // stdin can't seek, buffer it whole so parsers may backtrack.
+                let mut buf = vec![];
+                io::stdin().read_to_end(&mut buf)?;
+                Ok(Reader::new("<stdin>", Box::new(Cursor::new(buf))))
+            }
+            InputSource::Memory(s) => Ok(Reader::new(
+                "<memory>",
+                Box::new(Cursor::new(s.clone().into_bytes())),
+            )),
+            _ => open_file(&self.resolve(day, kind)?),
+        }
+    }
+
+    /// Numbered lines of the input, read from stdin as they arrive instead
+    /// of buffering it whole like `open` does.
+    pub fn lines(
+        &self,
+        day: u16,
+        kind: InputKind,
+    ) -> Result<Box<dyn Iterator<Item = Result<Line>>>> {
+        match self {
+            InputSource::Stdin => {
+                let path: Rc<str> = "<stdin>".into();
+                let lines = io::stdin().lines().enumerate().map(move |(i, text)| {
+                    Ok(Line {
+                        path: path.clone(),
+                        number: i + 1,
+                        text: text?,
+                    })
+                });
+                Ok(Box::new(lines))
+            }
+            _ => Ok(Box::new(self.open(day, kind)?.numbered_lines())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    /// A fresh folder under the system temp dir, removed when dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> TempDir {
+            let dir = env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            TempDir(dir)
+        }
+
+        fn write(&self, file: &str, contents: &str) -> PathBuf {
+            let path = self.0.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, contents).unwrap();
+            path
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn read(reader: Reader) -> String {
+        reader
+            .numbered_lines()
+            .map(|l| l.unwrap().text)
+            .collect::<Vec<_>>()
+            .join("|")
+    }
+
+    #[test]
+    fn input_kinds_round_trip() {
+        for (s, kind) in [
+            ("input", InputKind::Real),
+            ("sample", InputKind::Sample(1)),
+            ("sample3", InputKind::Sample(3)),
+        ] {
+            assert_eq!(s.parse::<InputKind>().unwrap(), kind);
+            assert_eq!(kind.to_string(), s);
+        }
+        assert_eq!("real".parse::<InputKind>().unwrap(), InputKind::Real);
+        assert_eq!(InputKind::Sample(2).filename(), "sample2.txt");
+        assert_eq!(format!("{:>7}", InputKind::Real), "  input");
+        for bad in ["sample0", "samplex", "sample256", "Input", ""] {
+            assert!(bad.parse::<InputKind>().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn dir_source_lays_out_days() {
+        let tmp = TempDir::new("dir-source");
+        let file = tmp.write("day07/sample2.txt", "a\nb\n");
+        let source = InputSource::Dir(tmp.0.clone());
+
+        assert_eq!(source.resolve(7, InputKind::Sample(2)).unwrap(), file);
+        assert_eq!(source.day_dir(7).unwrap(), tmp.0.join("day07"));
+        assert_eq!(read(source.open(7, InputKind::Sample(2)).unwrap()), "a|b");
+
+        let err = source.resolve(7, InputKind::Real).unwrap_err().to_string();
+        assert!(err.starts_with("input for day 7 not found"), "{}", err);
+        assert!(source.open(8, InputKind::Real).is_err());
+    }
+
+    #[test]
+    fn file_source_ignores_the_day() {
+        let tmp = TempDir::new("file-source");
+        let file = tmp.write("anything.txt", "x");
+        let source = InputSource::File(file.clone());
+
+        assert_eq!(source.resolve(3, InputKind::Sample(1)).unwrap(), file);
+        let reader = source.open(25, InputKind::Real).unwrap();
+        assert_eq!(reader.path(), file.display().to_string());
+        assert_eq!(read(reader), "x");
+        assert!(source.day_dir(3).is_err());
+    }
+
+    #[test]
+    fn search_walks_up_to_an_input_folder() {
+        let tmp = TempDir::new("search");
+        let file = tmp.write("input/day01/input.txt", "1");
+        let start = tmp.0.join("a/b");
+        fs::create_dir_all(&start).unwrap();
+
+        assert_eq!(search_file(None, &start, 1, InputKind::Real).unwrap(), file);
+        assert_eq!(
+            search_day_dir(None, &start, 2).unwrap(),
+            tmp.0.join("input/day02")
+        );
+
+        let err = search_file(None, &start, 1, InputKind::Sample(1))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains(INPUT_DIR_ENV), "{}", err);
+        let nearest = start.join("input/day01/sample.txt");
+        assert!(err.contains(&nearest.display().to_string()), "{}", err);
+    }
+
+    #[test]
+    fn env_var_overrides_the_search() {
+        let tmp = TempDir::new("env");
+        let file = tmp.write("elsewhere/day05/input.txt", "5");
+        tmp.write("input/day05/input.txt", "not this one");
+        let env_dir = Some(tmp.0.join("elsewhere").into_os_string());
+
+        assert_eq!(
+            search_file(env_dir.clone(), &tmp.0, 5, InputKind::Real).unwrap(),
+            file
+        );
+        assert!(search_file(env_dir.clone(), &tmp.0, 6, InputKind::Real).is_err());
+        assert_eq!(
+            search_day_dir(env_dir, &tmp.0, 6).unwrap(),
+            tmp.0.join("elsewhere/day06")
+        );
+    }
+
+    #[test]
+    fn memory_source_has_no_path() {
+        let source = InputSource::Memory("1 2\n3 4".to_string());
+        let reader = source.open(1, InputKind::Real).unwrap();
+        assert_eq!(reader.path(), "<memory>");
+        assert_eq!(read(reader), "1 2|3 4");
+        assert!(source.resolve(1, InputKind::Real).is_err());
+        assert!(source.day_dir(1).is_err());
+        assert!(InputSource::Stdin.resolve(1, InputKind::Real).is_err());
+    }
+}
@@ -0,0 +1,318 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Free space within `0..capacity`, kept as maximal gaps: adjacent gaps are
+/// always coalesced. Every operation is logarithmic in the capacity.
+#[derive(Debug, Clone)]
+pub struct IntervalAllocator {
+    /// Gap start to gap length.
+    gaps: BTreeMap<usize, usize>,
+    /// Gap length to the starts of gaps of that length, for best-fit.
+    by_size: BTreeMap<usize, BTreeSet<usize>>,
+    /// Length of the gap starting at each position (0 if none), so first-fit
+    /// can find the leftmost large enough gap.
+    tree: MaxTree,
+}
+
+impl IntervalAllocator {
+    /// An allocator over `0..capacity` with no free space.
+    pub fn new(capacity: usize) -> IntervalAllocator {
+        IntervalAllocator {
+            gaps: BTreeMap::new(),
+            by_size: BTreeMap::new(),
+            tree: MaxTree::new(capacity),
+        }
+    }
+
+    /// An allocator over `0..capacity` that is entirely free.
+    pub fn free_all(capacity: usize) -> IntervalAllocator {
+        let mut allocator = IntervalAllocator::new(capacity);
+        allocator.free(0, capacity);
+        allocator
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.tree.len
+    }
+
+    /// Gaps as `(start, len)`, left to right.
+    pub fn gaps(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.gaps.iter().map(|(&start, &len)| (start, len))
+    }
+
+    pub fn free_space(&self) -> usize {
+        self.gaps.values().sum()
+    }
+
+    /// The gap containing `pos`, as `(start, len)`.
+    pub fn gap_at(&self, pos: usize) -> Option<(usize, usize)> {
+        let (&start, &len) = self.gaps.range(..=pos).next_back()?;
+        (pos < start + len).then_some((start, len))
+    }
+
+    /// Marks `start..start + len` free, merging it with the gaps around it.
+    /// Panics if part of it already is free.
+    pub fn free(&mut self, start: usize, len: usize) {
+        if len == 0 {
+            return;
+        }
+        let end = start + len;
+        assert!(end <= self.capacity(), "range outside of the allocator");
+        assert!(
+            self.gaps
+                .range(..end)
+                .next_back()
+                .is_none_or(|(&s, &l)| s + l <= start),
+            "range is already partly free"
+        );
+
+        let (mut start, mut end) = (start, end);
+        if let Some((&s, &l)) = self.gaps.range(..start).next_back() {
+            if s + l == start {
+                self.remove_gap(s);
+                start = s;
+            }
+        }
+        if let Some(&l) = self.gaps.get(&end) {
+            self.remove_gap(end);
+            end += l;
+        }
+        self.insert_gap(start, end - start);
+    }
+
+    /// Marks `start..start + len` used, splitting the gap it lies in.
+    /// Returns `false`, changing nothing, if it is not entirely free.
+    pub fn reserve(&mut self, start: usize, len: usize) -> bool {
+        if len == 0 {
+            return true;
+        }
+        let Some((gap, gap_len)) = self.gap_at(start) else {
+            return false;
+        };
+        if start + len > gap + gap_len {
+            return false;
+        }
+        self.remove_gap(gap);
+        self.insert_gap(gap, start - gap);
+        self.insert_gap(start + len, gap + gap_len - start - len);
+        true
+    }
+
+    /// Start of the leftmost gap of at least `len`.
+    pub fn first_fit(&self, len: usize) -> Option<usize> {
+        self.first_fit_before(len, self.capacity())
+    }
+
+    /// Start of the leftmost gap of at least `len` starting before `limit`.
+    pub fn first_fit_before(&self, len: usize, limit: usize) -> Option<usize> {
+        self.tree.leftmost(len.max(1), limit)
+    }
+
+    /// Start of the smallest gap of at least `len`, the leftmost one among
+    /// equally small gaps.
+    pub fn best_fit(&self, len: usize) -> Option<usize> {
+        let (_, starts) = self.by_size.range(len.max(1)..).next()?;
+        starts.first().copied()
+    }
+
+    /// Reserves `len` at the start of the leftmost gap that fits it.
+    pub fn allocate_first_fit(&mut self, len: usize) -> Option<usize> {
+        self.allocate_first_fit_before(len, self.capacity())
+    }
+
+    /// Like `allocate_first_fit`, only considering gaps starting before
+    /// `limit`.
+    pub fn allocate_first_fit_before(&mut self, len: usize, limit: usize) -> Option<usize> {
+        let start = self.first_fit_before(len, limit)?;
+        self.reserve(start, len);
+        Some(start)
+    }
+
+    /// Reserves `len` at the start of the smallest gap that fits it.
+    pub fn allocate_best_fit(&mut self, len: usize) -> Option<usize> {
+        let start = self.best_fit(len)?;
+        self.reserve(start, len);
+        Some(start)
+    }
+
+    fn insert_gap(&mut self, start: usize, len: usize) {
+        if len == 0 {
+            return;
+        }
+        self.gaps.insert(start, len);
+        self.by_size.entry(len).or_default().insert(start);
+        self.tree.set(start, len);
+    }
+
+    fn remove_gap(&mut self, start: usize) {
+        let len = self.gaps.remove(&start).expect("gap exists");
+        let starts = self.by_size.get_mut(&len).expect("gap is indexed");
+        starts.remove(&start);
+        if starts.is_empty() {
+            self.by_size.remove(&len);
+        }
+        self.tree.set(start, 0);
+    }
+}
+
+/// Segment tree of maximums over `0..len`.
+#[derive(Debug, Clone)]
+struct MaxTree {
+    len: usize,
+    /// Number of leaves, a power of two.
+    size: usize,
+    /// `data[1]` is the root, the children of `i` are `2i` and `2i + 1`.
+    data: Vec<usize>,
+}
+
+impl MaxTree {
+    fn new(len: usize) -> MaxTree {
+        let size = len.next_power_of_two();
+        MaxTree {
+            len,
+            size,
+            data: vec![0; 2 * size],
+        }
+    }
+
+    fn set(&mut self, pos: usize, value: usize) {
+        let mut i = pos + self.size;
+        self.data[i] = value;
+        while i > 1 {
+            i /= 2;
+            self.data[i] = self.data[2 * i].max(self.data[2 * i + 1]);
+        }
+    }
+
+    /// Leftmost position before `limit` holding at least `min`.
+    fn leftmost(&self, min: usize, limit: usize) -> Option<usize> {
+        self.descend(1, 0, self.size, min, limit.min(self.len))
+    }
+
+    fn descend(
+        &self,
+        node: usize,
+        lo: usize,
+        hi: usize,
+        min: usize,
+        limit: usize,
+    ) -> Option<usize> {
+        if lo >= limit || self.data[node] < min {
+            return None;
+        }
+        if hi - lo == 1 {
+            return Some(lo);
+        }
+        let mid = (lo + hi) / 2;
+        self.descend(2 * node, lo, mid, min, limit)
+            .or_else(|| self.descend(2 * node + 1, mid, hi, min, limit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Rng;
+
+    fn gaps(allocator: &IntervalAllocator) -> Vec<(usize, usize)> {
+        allocator.gaps().collect()
+    }
+
+    #[test]
+    fn freeing_coalesces_neighbours() {
+        let mut a = IntervalAllocator::new(20);
+        a.free(2, 3);
+        a.free(10, 2);
+        assert_eq!(gaps(&a), vec![(2, 3), (10, 2)]);
+        // Touching the gap on the left only, then both at once.
+        a.free(5, 2);
+        assert_eq!(gaps(&a), vec![(2, 5), (10, 2)]);
+        a.free(7, 3);
+        assert_eq!(gaps(&a), vec![(2, 10)]);
+        a.free(12, 8);
+        a.free(0, 2);
+        assert_eq!(gaps(&a), vec![(0, 20)]);
+        assert_eq!(a.free_space(), 20);
+    }
+
+    #[test]
+    #[should_panic(expected = "already partly free")]
+    fn freeing_twice_panics() {
+        let mut a = IntervalAllocator::new(10);
+        a.free(2, 4);
+        a.free(5, 2);
+    }
+
+    #[test]
+    fn reserve_splits_gaps() {
+        let mut a = IntervalAllocator::free_all(10);
+        assert!(a.reserve(3, 2));
+        assert_eq!(gaps(&a), vec![(0, 3), (5, 5)]);
+        assert!(!a.reserve(2, 2));
+        assert!(!a.reserve(4, 1));
+        assert_eq!(gaps(&a), vec![(0, 3), (5, 5)]);
+        assert!(a.reserve(0, 3));
+        assert!(a.reserve(9, 0));
+        assert_eq!(a.gap_at(7), Some((5, 5)));
+        assert_eq!(a.gap_at(4), None);
+    }
+
+    #[test]
+    fn first_and_best_fit() {
+        let mut a = IntervalAllocator::new(30);
+        a.free(0, 4);
+        a.free(6, 2);
+        a.free(10, 3);
+        a.free(20, 3);
+        assert_eq!(a.first_fit(3), Some(0));
+        assert_eq!(a.best_fit(3), Some(10));
+        assert_eq!(a.best_fit(5), None);
+        assert_eq!(a.first_fit_before(3, 10), Some(0));
+        assert_eq!(a.first_fit_before(3, 0), None);
+
+        assert_eq!(a.allocate_best_fit(3), Some(10));
+        assert_eq!(a.allocate_best_fit(3), Some(20));
+        assert_eq!(a.allocate_first_fit(2), Some(0));
+        assert_eq!(gaps(&a), vec![(2, 2), (6, 2)]);
+        assert_eq!(a.allocate_first_fit_before(2, 5), Some(2));
+        assert_eq!(a.allocate_first_fit_before(2, 5), None);
+        assert_eq!(a.allocate_first_fit(2), Some(6));
+        assert_eq!(a.free_space(), 0);
+    }
+
+    #[test]
+    fn matches_a_bitmap() {
+        let mut rng = Rng::new(0x9e37_79b9_7f4a_7c15);
+        let mut next = |n: usize| rng.below(n);
+        const N: usize = 64;
+        let mut free = [true; N];
+        let mut a = IntervalAllocator::free_all(N);
+        for _ in 0..2000 {
+            let (start, len) = (next(N), 1 + next(6));
+            let len = len.min(N - start);
+            if next(2) == 0 {
+                let reserved = a.reserve(start, len);
+                assert_eq!(reserved, free[start..start + len].iter().all(|&f| f));
+                if reserved {
+                    free[start..start + len].fill(false);
+                }
+            } else if free[start..start + len].iter().all(|&f| !f) {
+                a.free(start, len);
+                free[start..start + len].fill(true);
+            }
+
+            let mut expected = vec![];
+            let mut i = 0;
+            while i < N {
+                let run = free[i..].iter().take_while(|&&f| f).count();
+                if run > 0 {
+                    expected.push((i, run));
+                }
+                i += run.max(1);
+            }
+            assert_eq!(gaps(&a), expected);
+            let want = 1 + next(4);
+            let first = expected.iter().find(|&&(_, l)| l >= want).map(|g| g.0);
+            assert_eq!(a.first_fit(want), first);
+        }
+    }
+}
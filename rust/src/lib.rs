@@ -1,13 +1,34 @@
 use anyhow::Result;
-use std::{env::current_dir, fs::File, io::BufReader};
 
-pub fn read(day: u16, sample: bool) -> Result<BufReader<File>> {
-    let input_folder = current_dir()?.parent().unwrap().join("input");
-    let input_filename = if sample { "sample.txt" } else { "input.txt" };
-    let file = input_folder
-        .join(format!("day{:0>2}", day))
-        .join(input_filename);
+use input::{InputKind, InputSource, Reader};
 
-    let f = File::open(file)?;
-    Ok(BufReader::new(f))
+pub mod answer;
+pub mod bench;
+pub mod bitgrid;
+pub mod combinatorics;
+pub mod counter;
+pub mod cycle;
+pub mod days;
+pub mod error;
+pub mod geom;
+pub mod graph;
+pub mod grid;
+pub mod history;
+pub mod input;
+pub mod interval;
+pub mod linalg;
+pub mod numtheory;
+pub mod parser;
+pub mod region;
+pub mod report;
+pub mod runner;
+pub mod search;
+pub mod solution;
+#[cfg(test)]
+mod testing;
+pub mod unionfind;
+pub mod verify;
+
+pub fn read(day: u16, kind: InputKind) -> Result<Reader> {
+    InputSource::Search.open(day, kind)
 }
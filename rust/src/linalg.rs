@@ -0,0 +1,397 @@
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use crate::numtheory::{extended_gcd, gcd};
+
+/// An exact fraction, always in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { num: 0, den: 1 };
+    pub const ONE: Rational = Rational { num: 1, den: 1 };
+
+    pub fn new(num: i128, den: i128) -> Rational {
+        assert!(den != 0, "zero denominator");
+        let g = gcd(num, den) * den.signum();
+        Rational {
+            num: num / g,
+            den: den / g,
+        }
+    }
+
+    pub fn numerator(self) -> i128 {
+        self.num
+    }
+
+    pub fn denominator(self) -> i128 {
+        self.den
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.num == 0
+    }
+
+    pub fn is_integer(self) -> bool {
+        self.den == 1
+    }
+
+    /// The value if it is a whole number.
+    pub fn to_integer(self) -> Option<i128> {
+        self.is_integer().then_some(self.num)
+    }
+
+    pub fn floor(self) -> i128 {
+        self.num.div_euclid(self.den)
+    }
+
+    pub fn ceil(self) -> i128 {
+        -(-self).floor()
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(n: i128) -> Rational {
+        Rational { num: n, den: 1 }
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Rational {
+        Rational::from(n as i128)
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        (self.num * other.den).cmp(&(other.num * self.den))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: Rational) -> Rational {
+        Rational::new(self.num * rhs.den + rhs.num * self.den, self.den * rhs.den)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: Rational) -> Rational {
+        self + -rhs
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: Rational) -> Rational {
+        Rational::new(self.num * rhs.num, self.den * rhs.den)
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    fn div(self, rhs: Rational) -> Rational {
+        assert!(!rhs.is_zero(), "division by zero");
+        Rational::new(self.num * rhs.den, self.den * rhs.num)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+/// Solutions of `A x = b`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Solution<T> {
+    None,
+    Unique(Vec<T>),
+    /// Every `particular + t_1 * basis[0] + ... + t_k * basis[k - 1]`, the
+    /// `t_i` ranging over the same numbers as the solution.
+    Parametric {
+        particular: Vec<T>,
+        basis: Vec<Vec<T>>,
+    },
+}
+
+fn check_shape(a: &[Vec<i64>], b: &[i64]) -> usize {
+    assert_eq!(a.len(), b.len(), "one right-hand side per equation");
+    let n = a.first().map_or(0, Vec::len);
+    assert!(a.iter().all(|row| row.len() == n), "ragged matrix");
+    n
+}
+
+/// Rational solutions of `A x = b` by Gaussian elimination over exact
+/// fractions. `a` has one row per equation and need not be square.
+pub fn solve(a: &[Vec<i64>], b: &[i64]) -> Solution<Rational> {
+    let n = check_shape(a, b);
+    let mut m: Vec<Vec<Rational>> = a
+        .iter()
+        .zip(b)
+        .map(|(row, &rhs)| {
+            row.iter()
+                .chain([&rhs])
+                .map(|&v| Rational::from(v))
+                .collect()
+        })
+        .collect();
+
+    // Reduced row echelon form.
+    let mut pivots = vec![];
+    for col in 0..n {
+        let row = pivots.len();
+        let Some(p) = (row..m.len()).find(|&r| !m[r][col].is_zero()) else {
+            continue;
+        };
+        m.swap(row, p);
+        let pivot = m[row][col];
+        for v in &mut m[row] {
+            *v = *v / pivot;
+        }
+        let pivot_row = m[row].clone();
+        for (r, other) in m.iter_mut().enumerate() {
+            let factor = other[col];
+            if r != row && !factor.is_zero() {
+                for (v, &p) in other[col..].iter_mut().zip(&pivot_row[col..]) {
+                    *v = *v - factor * p;
+                }
+            }
+        }
+        pivots.push(col);
+    }
+
+    if m[pivots.len()..].iter().any(|row| !row[n].is_zero()) {
+        return Solution::None;
+    }
+
+    let mut particular = vec![Rational::ZERO; n];
+    for (row, &col) in pivots.iter().enumerate() {
+        particular[col] = m[row][n];
+    }
+    if pivots.len() == n {
+        return Solution::Unique(particular);
+    }
+
+    let basis = (0..n)
+        .filter(|c| !pivots.contains(c))
+        .map(|free| {
+            let mut v = vec![Rational::ZERO; n];
+            v[free] = Rational::ONE;
+            for (row, &col) in pivots.iter().enumerate() {
+                v[col] = -m[row][free];
+            }
+            v
+        })
+        .collect();
+    Solution::Parametric { particular, basis }
+}
+
+/// Integer solutions of `A x = b`. A parametric result is the whole lattice
+/// of them, the `t_i` ranging over the integers. Solutions are `i128` since
+/// those of an `i64` system need not fit in an `i64`.
+pub fn solve_integer(a: &[Vec<i64>], b: &[i64]) -> Solution<i128> {
+    let n = check_shape(a, b);
+    let mut h: Vec<Vec<i128>> = a
+        .iter()
+        .map(|row| row.iter().map(|&v| v as i128).collect())
+        .collect();
+    let mut u: Vec<Vec<i128>> = (0..n)
+        .map(|i| (0..n).map(|j| (i == j) as i128).collect())
+        .collect();
+
+    // Unimodular column operations, applied to both `h` and `u`, bring `h`
+    // to column echelon form with `A u = h`. Then `A x = b` is `h y = b`
+    // with `x = u y`.
+    let mut pivots = vec![];
+    for i in 0..h.len() {
+        let col = pivots.len();
+        if col == n {
+            break;
+        }
+        for j in col + 1..n {
+            let (x, y) = (h[i][col], h[i][j]);
+            if y == 0 {
+                continue;
+            }
+            let (g, p, q) = extended_gcd(x, y);
+            let (xg, yg) = (x / g, y / g);
+            for m in [&mut h, &mut u] {
+                for row in m.iter_mut() {
+                    let (c, d) = (row[col], row[j]);
+                    row[col] = p * c + q * d;
+                    row[j] = -yg * c + xg * d;
+                }
+            }
+        }
+        if h[i][col] != 0 {
+            pivots.push(i);
+        }
+    }
+
+    // Forward substitution, checking the rows without a pivot too.
+    let rank = pivots.len();
+    let mut y = vec![0i128; rank];
+    let mut solved = 0;
+    for (i, row) in h.iter().enumerate() {
+        let known: i128 = (0..solved).map(|k| row[k] * y[k]).sum();
+        let rest = b[i] as i128 - known;
+        if solved < rank && pivots[solved] == i {
+            if rest % row[solved] != 0 {
+                return Solution::None;
+            }
+            y[solved] = rest / row[solved];
+            solved += 1;
+        } else if rest != 0 {
+            return Solution::None;
+        }
+    }
+
+    let particular: Vec<i128> = u
+        .iter()
+        .map(|row| (0..rank).map(|k| row[k] * y[k]).sum())
+        .collect();
+    if rank == n {
+        return Solution::Unique(particular);
+    }
+
+    let basis = (rank..n)
+        .map(|k| u.iter().map(|row| row[k]).collect())
+        .collect();
+    Solution::Parametric { particular, basis }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(num: i128, den: i128) -> Rational {
+        Rational::new(num, den)
+    }
+
+    #[test]
+    fn rationals_stay_reduced() {
+        assert_eq!(r(4, -6), r(-2, 3));
+        assert_eq!(r(-2, 3).denominator(), 3);
+        assert_eq!(r(1, 2) + r(1, 3), r(5, 6));
+        assert_eq!(r(1, 2) - r(1, 2), Rational::ZERO);
+        assert_eq!(r(2, 3) * r(3, 4), r(1, 2));
+        assert_eq!(r(1, 2) / r(1, 4), Rational::from(2i64));
+        assert!(r(1, 3) < r(1, 2));
+        assert_eq!(r(-7, 2).floor(), -4);
+        assert_eq!(r(-7, 2).ceil(), -3);
+        assert_eq!(r(6, 3).to_integer(), Some(2));
+        assert_eq!(r(-5, 3).to_string(), "-5/3");
+    }
+
+    #[test]
+    fn unique_rational_solution() {
+        let a = [vec![2, 1], vec![1, 3]];
+        assert_eq!(solve(&a, &[3, 5]), Solution::Unique(vec![r(4, 5), r(7, 5)]));
+    }
+
+    #[test]
+    fn inconsistent_system() {
+        let a = [vec![1, 1], vec![2, 2]];
+        assert_eq!(solve(&a, &[1, 3]), Solution::None);
+        assert_eq!(solve_integer(&a, &[1, 3]), Solution::None);
+    }
+
+    #[test]
+    fn parametric_rational_solution() {
+        let a = [vec![1, 2, 3]];
+        let Solution::Parametric { particular, basis } = solve(&a, &[6]) else {
+            panic!("expected a parametric solution");
+        };
+        assert_eq!(basis.len(), 2);
+        let dot = |x: &[Rational]| {
+            (0..3).fold(Rational::ZERO, |acc, i| {
+                acc + Rational::from(a[0][i]) * x[i]
+            })
+        };
+        assert_eq!(dot(&particular), Rational::from(6i64));
+        for v in &basis {
+            assert_eq!(dot(v), Rational::ZERO);
+        }
+    }
+
+    #[test]
+    fn rational_solution_that_is_not_integer() {
+        let a = [vec![2, 0], vec![0, 3]];
+        assert_eq!(solve(&a, &[1, 1]), Solution::Unique(vec![r(1, 2), r(1, 3)]));
+        assert_eq!(solve_integer(&a, &[1, 1]), Solution::None);
+        assert_eq!(solve_integer(&a, &[4, 9]), Solution::Unique(vec![2, 3]));
+    }
+
+    #[test]
+    fn integer_lattice() {
+        // Pairwise the coefficients share a factor, together they do not.
+        let a = [vec![6, 10, 15]];
+        let Solution::Parametric { particular, basis } = solve_integer(&a, &[1]) else {
+            panic!("expected a parametric solution");
+        };
+        let dot = |x: &[i128]| 6 * x[0] + 10 * x[1] + 15 * x[2];
+        assert_eq!(dot(&particular), 1);
+        assert_eq!(basis.len(), 2);
+        for v in &basis {
+            assert_eq!(dot(v), 0);
+        }
+        // The basis spans every solution: its 2x2 minors have gcd 1.
+        let minor = |i: usize, j: usize| basis[0][i] * basis[1][j] - basis[0][j] * basis[1][i];
+        let g = gcd(gcd(minor(0, 1), minor(0, 2)), minor(1, 2));
+        assert_eq!(g, 1);
+    }
+
+    #[test]
+    fn integer_solution_beyond_i64() {
+        let max = i64::MAX;
+        let a = [vec![1, -1], vec![0, 1]];
+        assert_eq!(
+            solve_integer(&a, &[max, max]),
+            Solution::Unique(vec![2 * max as i128, max as i128])
+        );
+    }
+
+    #[test]
+    fn more_equations_than_unknowns() {
+        let a = [vec![1, 0], vec![0, 1], vec![1, 1]];
+        assert_eq!(solve_integer(&a, &[2, 3, 5]), Solution::Unique(vec![2, 3]));
+        assert_eq!(solve_integer(&a, &[2, 3, 6]), Solution::None);
+        assert_eq!(
+            solve(&a, &[2, 3, 5]),
+            Solution::Unique(vec![r(2, 1), r(3, 1)])
+        );
+    }
+}
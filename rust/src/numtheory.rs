@@ -0,0 +1,195 @@
+use std::ops::{Div, Mul, Neg, Rem, Sub};
+
+/// Number of decimal digits of `n`, 1 for 0.
+pub fn digit_count(n: u64) -> u32 {
+    n.checked_ilog10().map_or(1, |log| log + 1)
+}
+
+/// `10^exp`, panicking if it does not fit in a `u64` (`exp > 19`).
+pub fn pow10(exp: u32) -> u64 {
+    10u64
+        .checked_pow(exp)
+        .unwrap_or_else(|| panic!("10^{} overflows u64", exp))
+}
+
+/// The number written as the digits of `a` followed by those of `b`, or
+/// `None` on overflow.
+pub fn concat_digits(a: u64, b: u64) -> Option<u64> {
+    a.checked_mul(10u64.checked_pow(digit_count(b))?)?
+        .checked_add(b)
+}
+
+/// Signed integer types the gcd functions work on.
+pub trait Integer:
+    Copy
+    + Ord
+    + Neg<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+            }
+        )*
+    };
+}
+
+impl_integer!(i32, i64, i128);
+
+/// Greatest common divisor, never negative. `gcd(0, 0)` is 0.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a, b);
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    if a < T::ZERO {
+        -a
+    } else {
+        a
+    }
+}
+
+/// Least common multiple, never negative. 0 if either argument is.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+    let l = a / gcd(a, b) * b;
+    if l < T::ZERO {
+        -l
+    } else {
+        l
+    }
+}
+
+/// `(g, x, y)` with `g = gcd(a, b)` and `a * x + b * y == g`, for any signs.
+pub fn extended_gcd<T: Integer>(a: T, b: T) -> (T, T, T) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (T::ONE, T::ZERO);
+    let (mut y0, mut y1) = (T::ZERO, T::ONE);
+    while r1 != T::ZERO {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < T::ZERO {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// `x` in `0..m` with `a * x ≡ 1 (mod m)`, if `a` and `m` are coprime.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    assert!(m > 0, "modulus must be positive");
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    if g != 1 {
+        return None;
+    }
+    Some(x.rem_euclid(m))
+}
+
+/// Solves `x ≡ r (mod m)` for every `(r, m)` of `congruences`, moduli not
+/// needing to be coprime. Returns `(x, lcm of the moduli)` with `x` in
+/// `0..lcm`, or `None` if the congruences contradict each other.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let (mut x, mut m): (i128, i128) = (0, 1);
+    for &(r, n) in congruences {
+        assert!(n > 0, "modulus must be positive");
+        let (r, n) = (r as i128, n as i128);
+
+        // x + m * t ≡ r (mod n)  <=>  m * t ≡ r - x (mod n)
+        let (g, p, _) = extended_gcd(m, n);
+        let diff = r - x;
+        if diff % g != 0 {
+            return None;
+        }
+        let step = n / g;
+        let t = (diff / g % step * p).rem_euclid(step);
+        x += m * t;
+        m *= step;
+        x = x.rem_euclid(m);
+    }
+    Some((i64::try_from(x).ok()?, i64::try_from(m).ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digits() {
+        assert_eq!(digit_count(0), 1);
+        assert_eq!(digit_count(9), 1);
+        assert_eq!(digit_count(10), 2);
+        assert_eq!(digit_count(u64::MAX), 20);
+        assert_eq!(pow10(0), 1);
+        assert_eq!(pow10(19), 10_000_000_000_000_000_000);
+        assert_eq!(concat_digits(12, 345), Some(12345));
+        assert_eq!(concat_digits(12, 0), Some(120));
+        assert_eq!(concat_digits(u64::MAX, 1), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows")]
+    fn pow10_overflow() {
+        pow10(20);
+    }
+
+    #[test]
+    fn gcd_and_lcm_ignore_signs() {
+        assert_eq!(gcd(12, -18), 6);
+        assert_eq!(gcd(-12i128, -18), 6);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(gcd(0, -5), 5);
+        assert_eq!(lcm(4, -6), 12);
+        assert_eq!(lcm(0, 6), 0);
+    }
+
+    #[test]
+    fn extended_gcd_any_signs() {
+        for (a, b) in [
+            (240i64, 46),
+            (-240, 46),
+            (240, -46),
+            (0, 7),
+            (7, 0),
+            (-3, -9),
+        ] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+        let big = i64::MAX as i128 * 6;
+        let (g, x, y) = extended_gcd(big, 15);
+        assert_eq!((g, big * x + 15 * y), (3, 3));
+    }
+
+    #[test]
+    fn inverses() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn chinese_remainders() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // Moduli sharing a factor, consistent or not.
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+}
@@ -0,0 +1,474 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::error::{Line, ParseError};
+
+pub type PResult<'a, T> = Result<(&'a str, T), Error>;
+
+/// A parser failure. It records how much input was left rather than a
+/// position, so it can be located in any input the failing one is a suffix
+/// of, like the whole line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    pub remaining: usize,
+    pub expected: String,
+}
+
+impl Error {
+    pub fn new(input: &str, expected: impl Display) -> Error {
+        Error {
+            remaining: input.len(),
+            expected: expected.to_string(),
+        }
+    }
+
+    /// Byte offset of the failure in `input`.
+    pub fn offset(&self, input: &str) -> usize {
+        input.len().saturating_sub(self.remaining)
+    }
+
+    /// Of two failures, the one that got further.
+    fn furthest(self, other: Error) -> Error {
+        if other.remaining < self.remaining {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected {}", self.expected)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Takes the remaining input and returns what is left after it together
+/// with its output. Parsers never copy the input they recognise: `literal`,
+/// `digits` and `take_while` hand back slices of it.
+pub trait Parser<'a, T> {
+    fn parse(&mut self, input: &'a str) -> PResult<'a, T>;
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: FnMut(&'a str) -> PResult<'a, T>,
+{
+    fn parse(&mut self, input: &'a str) -> PResult<'a, T> {
+        self(input)
+    }
+}
+
+/// Matches `lit` exactly.
+pub fn literal<'a, 'b>(lit: &'b str) -> impl FnMut(&'a str) -> PResult<'a, &'a str> + 'b {
+    move |input: &'a str| match input.strip_prefix(lit) {
+        Some(rest) => Ok((rest, &input[..lit.len()])),
+        None => Err(Error::new(input, format!("`{}`", lit))),
+    }
+}
+
+/// The longest prefix whose characters satisfy `pred`, possibly empty.
+pub fn take_while<'a>(
+    mut pred: impl FnMut(char) -> bool,
+) -> impl FnMut(&'a str) -> PResult<'a, &'a str> {
+    move |input: &'a str| {
+        let end = input.find(|c| !pred(c)).unwrap_or(input.len());
+        Ok((&input[end..], &input[..end]))
+    }
+}
+
+/// Like `take_while`, failing with `expected` if nothing matches.
+pub fn take_while1<'a>(
+    pred: impl FnMut(char) -> bool,
+    expected: &'static str,
+) -> impl FnMut(&'a str) -> PResult<'a, &'a str> {
+    let mut take = take_while(pred);
+    move |input: &'a str| match take(input)? {
+        (_, "") => Err(Error::new(input, expected)),
+        ok => Ok(ok),
+    }
+}
+
+/// One or more ASCII digits.
+pub fn digits<'a>() -> impl FnMut(&'a str) -> PResult<'a, &'a str> {
+    take_while1(|c| c.is_ascii_digit(), "digits")
+}
+
+/// A single decimal digit and its value.
+pub fn digit<'a>() -> impl FnMut(&'a str) -> PResult<'a, u32> {
+    move |input: &'a str| {
+        let mut chars = input.chars();
+        match chars.next().and_then(|c| c.to_digit(10)) {
+            Some(d) => Ok((chars.as_str(), d)),
+            None => Err(Error::new(input, "a digit")),
+        }
+    }
+}
+
+fn number<'a, T: FromStr>(input: &'a str, rest: &'a str) -> PResult<'a, T> {
+    let text = &input[..input.len() - rest.len()];
+    match text.parse() {
+        Ok(n) => Ok((rest, n)),
+        Err(_) => Err(Error::new(input, "a number in range")),
+    }
+}
+
+/// A number without sign.
+pub fn unsigned<'a, T: FromStr>() -> impl FnMut(&'a str) -> PResult<'a, T> {
+    let mut digits = digits();
+    move |input: &'a str| {
+        let (rest, _) = digits(input)?;
+        number(input, rest)
+    }
+}
+
+/// A number with an optional `+` or `-` sign.
+pub fn signed<'a, T: FromStr>() -> impl FnMut(&'a str) -> PResult<'a, T> {
+    let mut digits = digits();
+    move |input: &'a str| {
+        let unsigned = input.strip_prefix(['+', '-']).unwrap_or(input);
+        let (rest, _) = digits(unsigned)?;
+        number(input, rest)
+    }
+}
+
+/// Spaces and tabs, possibly none.
+pub fn space0<'a>() -> impl FnMut(&'a str) -> PResult<'a, &'a str> {
+    take_while(|c| c == ' ' || c == '\t')
+}
+
+/// At least one space or tab.
+pub fn space1<'a>() -> impl FnMut(&'a str) -> PResult<'a, &'a str> {
+    take_while1(|c| c == ' ' || c == '\t', "whitespace")
+}
+
+/// Succeeds only at the end of the input.
+pub fn end<'a>() -> impl FnMut(&'a str) -> PResult<'a, ()> {
+    move |input: &'a str| {
+        if input.is_empty() {
+            Ok((input, ()))
+        } else {
+            Err(Error::new(input, "end of input"))
+        }
+    }
+}
+
+pub fn map<'a, T, U>(
+    mut p: impl Parser<'a, T>,
+    mut f: impl FnMut(T) -> U,
+) -> impl FnMut(&'a str) -> PResult<'a, U> {
+    move |input: &'a str| {
+        let (rest, value) = p.parse(input)?;
+        Ok((rest, f(value)))
+    }
+}
+
+/// Replaces the output of `p` with `value`.
+pub fn value<'a, T, U: Clone>(
+    p: impl Parser<'a, T>,
+    value: U,
+) -> impl FnMut(&'a str) -> PResult<'a, U> {
+    map(p, move |_| value.clone())
+}
+
+pub fn pair<'a, T, U>(
+    mut first: impl Parser<'a, T>,
+    mut second: impl Parser<'a, U>,
+) -> impl FnMut(&'a str) -> PResult<'a, (T, U)> {
+    move |input: &'a str| {
+        let (rest, a) = first.parse(input)?;
+        let (rest, b) = second.parse(rest)?;
+        Ok((rest, (a, b)))
+    }
+}
+
+/// `first`, `sep` then `second`, keeping the outputs of `first` and
+/// `second`.
+pub fn separated_pair<'a, T, S, U>(
+    first: impl Parser<'a, T>,
+    sep: impl Parser<'a, S>,
+    second: impl Parser<'a, U>,
+) -> impl FnMut(&'a str) -> PResult<'a, (T, U)> {
+    pair(terminated(first, sep), second)
+}
+
+/// Runs `skip` then `p`, keeping the output of `p`.
+pub fn preceded<'a, S, T>(
+    skip: impl Parser<'a, S>,
+    p: impl Parser<'a, T>,
+) -> impl FnMut(&'a str) -> PResult<'a, T> {
+    map(pair(skip, p), |(_, value)| value)
+}
+
+/// Runs `p` then `skip`, keeping the output of `p`.
+pub fn terminated<'a, T, S>(
+    p: impl Parser<'a, T>,
+    skip: impl Parser<'a, S>,
+) -> impl FnMut(&'a str) -> PResult<'a, T> {
+    map(pair(p, skip), |(value, _)| value)
+}
+
+pub fn delimited<'a, O, T, C>(
+    open: impl Parser<'a, O>,
+    p: impl Parser<'a, T>,
+    close: impl Parser<'a, C>,
+) -> impl FnMut(&'a str) -> PResult<'a, T> {
+    preceded(open, terminated(p, close))
+}
+
+/// `p` surrounded by optional spaces.
+pub fn ws<'a, T>(p: impl Parser<'a, T>) -> impl FnMut(&'a str) -> PResult<'a, T> {
+    delimited(space0(), p, space0())
+}
+
+/// `Some` output of `p`, or `None` without consuming anything if it fails.
+pub fn optional<'a, T>(mut p: impl Parser<'a, T>) -> impl FnMut(&'a str) -> PResult<'a, Option<T>> {
+    move |input: &'a str| match p.parse(input) {
+        Ok((rest, value)) => Ok((rest, Some(value))),
+        Err(_) => Ok((input, None)),
+    }
+}
+
+/// `p` as many times as it matches, possibly none.
+pub fn many0<'a, T>(mut p: impl Parser<'a, T>) -> impl FnMut(&'a str) -> PResult<'a, Vec<T>> {
+    move |mut input: &'a str| {
+        let mut items = vec![];
+        while let Ok((rest, item)) = p.parse(input) {
+            if rest.len() == input.len() {
+                break;
+            }
+            items.push(item);
+            input = rest;
+        }
+        Ok((input, items))
+    }
+}
+
+/// One or more `item`s separated by `sep`. A trailing `sep` not followed by
+/// an item is left unconsumed.
+pub fn separated<'a, T, S>(
+    mut item: impl Parser<'a, T>,
+    mut sep: impl Parser<'a, S>,
+) -> impl FnMut(&'a str) -> PResult<'a, Vec<T>> {
+    move |input: &'a str| {
+        let (mut input, first) = item.parse(input)?;
+        let mut items = vec![first];
+        loop {
+            let Ok((rest, _)) = sep.parse(input) else {
+                break;
+            };
+            let Ok((rest, next)) = item.parse(rest) else {
+                break;
+            };
+            items.push(next);
+            input = rest;
+        }
+        Ok((input, items))
+    }
+}
+
+/// Alternatives tried in order, see `alt`.
+pub trait Alt<'a, T> {
+    fn choice(&mut self, input: &'a str) -> PResult<'a, T>;
+}
+
+macro_rules! impl_alt {
+    ($first:ident $($p:ident)+) => {
+        impl<'a, T, $first: Parser<'a, T>, $($p: Parser<'a, T>),+> Alt<'a, T> for ($first, $($p),+) {
+            #[allow(non_snake_case)]
+            fn choice(&mut self, input: &'a str) -> PResult<'a, T> {
+                let ($first, $($p),+) = self;
+                let mut error = match $first.parse(input) {
+                    Ok(ok) => return Ok(ok),
+                    Err(e) => e,
+                };
+                $(
+                    match $p.parse(input) {
+                        Ok(ok) => return Ok(ok),
+                        Err(e) => error = error.furthest(e),
+                    }
+                )+
+                Err(error)
+            }
+        }
+    };
+}
+
+impl_alt!(A B);
+impl_alt!(A B C);
+impl_alt!(A B C D);
+impl_alt!(A B C D E);
+
+/// The first of the parsers in the tuple that succeeds. On failure, reports
+/// the alternative that got furthest.
+pub fn alt<'a, T>(mut parsers: impl Alt<'a, T>) -> impl FnMut(&'a str) -> PResult<'a, T> {
+    move |input: &'a str| parsers.choice(input)
+}
+
+/// Runs `p` over the whole of `input`.
+pub fn parse_all<'a, T>(input: &'a str, p: impl Parser<'a, T>) -> Result<T, Error> {
+    let (_, value) = terminated(p, end()).parse(input)?;
+    Ok(value)
+}
+
+/// Every match of `p` in `input`, skipping a character wherever it fails.
+pub fn scan<'a, T: 'a>(
+    mut input: &'a str,
+    mut p: impl Parser<'a, T> + 'a,
+) -> impl Iterator<Item = T> + 'a {
+    std::iter::from_fn(move || {
+        while !input.is_empty() {
+            match p.parse(input) {
+                Ok((rest, value)) if rest.len() < input.len() => {
+                    input = rest;
+                    return Some(value);
+                }
+                _ => {
+                    let mut chars = input.chars();
+                    chars.next();
+                    input = chars.as_str();
+                }
+            }
+        }
+        None
+    })
+}
+
+/// Runs `p` over the whole line, ignoring surrounding whitespace, and
+/// points failures at the word where parsing stopped.
+pub fn parse_line<'a, T>(line: &'a Line, p: impl Parser<'a, T>) -> Result<T, ParseError> {
+    let text = line.text.trim();
+    parse_all(text, p).map_err(|e| {
+        let offset = e.offset(text);
+        let rest = &text[offset..];
+        let end = match rest.find(char::is_whitespace) {
+            Some(0) => rest.chars().next().map_or(0, char::len_utf8),
+            Some(end) => end,
+            None => rest.len(),
+        };
+        let word = &rest[..end];
+        line.error(word, &e)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(text: &str) -> Line {
+        Line {
+            path: "<test>".into(),
+            number: 3,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(unsigned::<u32>()("42 rest"), Ok((" rest", 42)));
+        assert_eq!(signed::<i32>()("-7,"), Ok((",", -7)));
+        assert_eq!(signed::<i32>()("+7"), Ok(("", 7)));
+        assert_eq!(digit()("93"), Ok(("3", 9)));
+        assert!(unsigned::<u32>()("-7").is_err());
+        assert!(signed::<i32>()("-").is_err());
+        let err = unsigned::<u8>()("300").unwrap_err();
+        assert_eq!(err.expected, "a number in range");
+    }
+
+    #[test]
+    fn combinators() {
+        let mut p = separated_pair(signed::<i32>(), ws(literal(",")), signed::<i32>());
+        assert_eq!(p("3 , -4!"), Ok(("!", (3, -4))));
+        assert_eq!(
+            delimited(literal("("), digits(), literal(")"))("(12)"),
+            Ok(("", "12"))
+        );
+        assert_eq!(optional(literal("x"))("y"), Ok(("y", None)));
+        assert_eq!(
+            many0(terminated(digit(), space0()))("1 2 3x"),
+            Ok(("x", vec![1, 2, 3]))
+        );
+        assert_eq!(many0(space0())("abc"), Ok(("abc", vec![])));
+    }
+
+    #[test]
+    fn separated_leaves_trailing_separator() {
+        let mut p = separated(unsigned::<u32>(), literal(","));
+        assert_eq!(p("1,2,3,"), Ok((",", vec![1, 2, 3])));
+        assert!(p("").is_err());
+    }
+
+    #[test]
+    fn alt_reports_furthest_failure() {
+        let mut p = alt((
+            value(literal("mul"), 1),
+            value(pair(literal("do"), literal("()")), 2),
+        ));
+        assert_eq!(p("do()"), Ok(("", 2)));
+        let err = p("don't").unwrap_err();
+        assert_eq!(err.offset("don't"), 2);
+        assert_eq!(err.to_string(), "expected `()`");
+    }
+
+    #[test]
+    fn parse_all_needs_whole_input() {
+        assert_eq!(parse_all("12", unsigned::<u32>()), Ok(12));
+        let err = parse_all("12a", unsigned::<u32>()).unwrap_err();
+        assert_eq!(
+            (err.offset("12a"), err.expected.as_str()),
+            (2, "end of input")
+        );
+    }
+
+    #[test]
+    fn scan_skips_garbage() {
+        let mul = preceded(
+            literal("mul("),
+            terminated(
+                separated_pair(unsigned::<u32>(), literal(","), unsigned()),
+                literal(")"),
+            ),
+        );
+        let found: Vec<_> = scan("xmul(2,4)%mul[3,7]mul(5,5)mul(32,64]", mul).collect();
+        assert_eq!(found, vec![(2, 4), (5, 5)]);
+    }
+
+    #[test]
+    fn parse_line_points_at_word() {
+        let l = line("move 3 from x  ");
+        let p = preceded(
+            pair(literal("move "), unsigned::<u32>()),
+            preceded(literal(" from "), unsigned::<u32>()),
+        );
+        let err = parse_line(&l, p).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 13));
+        assert_eq!(err.text, "x");
+        assert_eq!(err.message, "expected digits");
+
+        let ok = parse_line(&line("1 2 \t"), separated_pair(digit(), space1(), digit()));
+        assert_eq!(ok.unwrap(), (1, 2));
+    }
+
+    #[test]
+    fn parse_line_ignores_leading_whitespace() {
+        let p = || separated_pair(unsigned::<u32>(), space1(), unsigned::<u32>());
+        let indented = line("  \t3   4");
+        assert_eq!(parse_line(&indented, p()), Ok((3, 4)));
+
+        // Columns still count from the start of the untrimmed line.
+        let err = parse_line(&line("   3 x"), p()).unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (6, "x"));
+    }
+
+    #[test]
+    fn parse_line_with_non_ascii() {
+        let err = parse_line(&line("été 1"), unsigned::<u32>()).unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (1, "été"));
+        let err = parse_line(&line("é1 2€x"), unsigned::<u32>()).unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (1, "é1"));
+        let p = separated_pair(unsigned::<u32>(), space1(), unsigned::<u32>());
+        let err = parse_line(&line(" 1 2€x"), p).unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (5, "€x"));
+    }
+}
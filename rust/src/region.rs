@@ -0,0 +1,170 @@
+use crate::{
+    geom::{Direction, Point},
+    grid::{Grid, Pos},
+    unionfind,
+};
+
+/// Shape measurements of one orthogonally connected region.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub area: usize,
+    /// Cell edges between the region and anything else, the border included.
+    pub perimeter: usize,
+    /// Straight runs of fence, equal to the number of corners.
+    pub sides: usize,
+    /// Top-left cell of the bounding box.
+    pub min: Pos,
+    /// Bottom-right cell of the bounding box, inclusive.
+    pub max: Pos,
+    /// Areas of other cells fully enclosed by the region.
+    pub holes: usize,
+}
+
+/// Every region of a grid, with the label of each cell indexing `regions`.
+#[derive(Debug, Clone)]
+pub struct Regions {
+    pub labels: Grid<usize>,
+    pub regions: Vec<Region>,
+}
+
+/// Labels the regions of `grid` and measures them, two neighbouring cells
+/// belonging to the same region when `connected` says so. Regions are
+/// numbered in reading order of their first cell.
+pub fn analyse<T>(grid: &Grid<T>, connected: impl FnMut(&T, &T) -> bool) -> Regions {
+    let (labels, count) = unionfind::grid_components(grid, connected);
+    let mut regions = vec![
+        Region {
+            area: 0,
+            perimeter: 0,
+            sides: 0,
+            min: Point::new(i32::MAX, i32::MAX),
+            max: Point::new(i32::MIN, i32::MIN),
+            holes: 0,
+        };
+        count
+    ];
+    let same = |label: usize, pos: Pos| labels.get(pos) == Some(&label);
+
+    for (pos, &label) in labels.iter() {
+        let region = &mut regions[label];
+        region.area += 1;
+        region.min = Point::new(region.min.x.min(pos.x), region.min.y.min(pos.y));
+        region.max = Point::new(region.max.x.max(pos.x), region.max.y.max(pos.y));
+        region.perimeter += Direction::CARDINAL
+            .iter()
+            .filter(|&&d| !same(label, pos + d))
+            .count();
+        // A convex corner has neither side in the region, a concave one
+        // has both but not the diagonal between them.
+        region.sides += Direction::DIAGONAL
+            .iter()
+            .filter(|&&d| {
+                let a = same(label, pos + d.turn_left45());
+                let b = same(label, pos + d.turn_right45());
+                (!a && !b) || (a && b && !same(label, pos + d))
+            })
+            .count();
+    }
+
+    // Holes follow from the Euler characteristic `V - E + F` of the region
+    // seen as a union of closed squares, which is `1 - holes`. `F` is the
+    // area and `E` is `(4 * area + perimeter) / 2`; `V` needs counting.
+    let mut vertices = vec![0; count];
+    for x in 0..=labels.height() as i32 {
+        for y in 0..=labels.width() as i32 {
+            let mut around: Vec<usize> = [(-1, -1), (-1, 0), (0, -1), (0, 0)]
+                .into_iter()
+                .filter_map(|(dx, dy)| labels.get(Point::new(x + dx, y + dy)).copied())
+                .collect();
+            around.sort_unstable();
+            around.dedup();
+            for label in around {
+                vertices[label] += 1;
+            }
+        }
+    }
+    for (region, v) in regions.iter_mut().zip(vertices) {
+        let edges = (4 * region.area + region.perimeter) / 2;
+        region.holes = 1 + edges - v - region.area;
+    }
+
+    Regions { labels, regions }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn regions(text: &str) -> Vec<(char, Region)> {
+        let grid: Grid<char> = text.parse().unwrap();
+        let Regions { labels, regions } = analyse(&grid, |a, b| a == b);
+        regions
+            .into_iter()
+            .enumerate()
+            .map(|(label, region)| {
+                let pos = labels.find(&label).unwrap();
+                (grid[pos], region)
+            })
+            .collect()
+    }
+
+    /// `(plant, area, perimeter, sides, holes)` of every region.
+    fn measures(text: &str) -> Vec<(char, usize, usize, usize, usize)> {
+        regions(text)
+            .into_iter()
+            .map(|(c, r)| (c, r.area, r.perimeter, r.sides, r.holes))
+            .collect()
+    }
+
+    #[test]
+    fn simple_regions() {
+        assert_eq!(
+            measures("AAAA\nBBCD\nBBCC\nEEEC"),
+            vec![
+                ('A', 4, 10, 4, 0),
+                ('B', 4, 8, 4, 0),
+                ('C', 4, 10, 8, 0),
+                ('D', 1, 4, 4, 0),
+                ('E', 3, 8, 4, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn bounding_boxes() {
+        let found = regions("AAAA\nBBCD\nBBCC\nEEEC");
+        let (_, c) = &found[2];
+        assert_eq!((c.min, c.max), (Point::new(1, 2), Point::new(3, 3)));
+    }
+
+    #[test]
+    fn separate_holes() {
+        let found = measures("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO");
+        assert_eq!(found[0], ('O', 21, 36, 20, 4));
+        assert_eq!(found.len(), 5);
+        assert!(found[1..].iter().all(|&m| m == ('X', 1, 4, 4, 0)));
+    }
+
+    #[test]
+    fn holes_touching_diagonally() {
+        // The two B squares meet at a point, where the A cells around them
+        // pinch: they count as two holes.
+        let found = measures("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA");
+        assert_eq!(found[0], ('A', 28, 40, 12, 2));
+    }
+
+    #[test]
+    fn hole_closed_by_diagonal_pinch() {
+        // The ring of A only closes where (0, 1) and (1, 0) touch corners.
+        let found = measures(".AAA\nA..A\nAAAA");
+        let a = found.iter().find(|m| m.0 == 'A').unwrap();
+        assert_eq!(*a, ('A', 9, 20, 10, 1));
+        assert_eq!(found.iter().filter(|m| m.0 == '.').count(), 2);
+    }
+
+    #[test]
+    fn sides_of_an_e() {
+        let found = measures("EEEEE\nEXXXX\nEEEEE\nEXXXX\nEEEEE");
+        assert_eq!(found[0], ('E', 17, 36, 12, 0));
+    }
+}
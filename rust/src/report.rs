@@ -0,0 +1,303 @@
+use std::{str::FromStr, time::Duration};
+
+use anyhow::bail;
+
+use crate::{answer::Answer, input::InputKind, runner::DayResult};
+
+/// Receives the results of each day once it finished running.
+pub trait Reporter {
+    fn report(&mut self, result: &DayResult);
+    /// Called once all days ran, `elapsed` being the wall-clock time.
+    fn finish(&mut self, _elapsed: Duration) {}
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Plain,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plain" => Ok(Format::Plain),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => bail!("invalid format `{}`, expected plain, json or csv", s),
+        }
+    }
+}
+
+impl Format {
+    /// `parts` lets the machine-readable formats emit a row per part even for
+    /// days that failed to parse; `headers` and `timings` only affect plain.
+    pub fn reporter(self, parts: &[u8], headers: bool, timings: bool) -> Box<dyn Reporter> {
+        match self {
+            Format::Plain => Box::new(Plain::new(headers, timings)),
+            Format::Json => Box::new(Json::new(parts)),
+            Format::Csv => Box::new(Csv::new(parts)),
+        }
+    }
+}
+
+/// Prints bare answers, one per line, optionally under a `=====dayNN=====`
+/// header and followed by the time each part took. Errors go to stderr.
+#[derive(Default)]
+pub struct Plain {
+    pub headers: bool,
+    pub timings: bool,
+    total: Duration,
+}
+
+impl Plain {
+    pub fn new(headers: bool, timings: bool) -> Plain {
+        Plain {
+            headers,
+            timings,
+            ..Default::default()
+        }
+    }
+}
+
+impl Reporter for Plain {
+    fn report(&mut self, result: &DayResult) {
+        if self.headers {
+            println!();
+            println!("=====day{:0>2}=====", result.day);
+        }
+        if let Err(e) = &result.parse {
+            eprintln!("day{:0>2} parse: {:#}", result.day, e);
+        }
+        for part in &result.parts {
+            match &part.answer {
+                Ok(answer) if self.timings => println!("{} ({:.2?})", answer, part.duration),
+                Ok(answer) => println!("{}", answer),
+                Err(e) => eprintln!("day{:0>2} part{}: {:#}", result.day, part.part, e),
+            }
+        }
+        self.total += result.duration();
+    }
+
+    fn finish(&mut self, elapsed: Duration) {
+        if self.timings {
+            println!();
+            println!("total {:.2?} (wall clock {:.2?})", self.total, elapsed);
+        }
+    }
+}
+
+/// One part of one day, flattened for the machine-readable formats. A day
+/// that failed to parse yields a row per requested part carrying the error.
+struct Row {
+    day: u16,
+    part: u8,
+    kind: InputKind,
+    answer: Option<Answer>,
+    duration: Duration,
+    error: Option<String>,
+}
+
+fn rows(result: &DayResult, parts: &[u8]) -> Vec<Row> {
+    match &result.parse {
+        Err(e) => parts
+            .iter()
+            .map(|&part| Row {
+                day: result.day,
+                part,
+                kind: result.kind,
+                answer: None,
+                duration: Duration::ZERO,
+                error: Some(format!("{:#}", e)),
+            })
+            .collect(),
+        Ok(_) => result
+            .parts
+            .iter()
+            .map(|part| Row {
+                day: result.day,
+                part: part.part,
+                kind: result.kind,
+                answer: part.answer.as_ref().ok().cloned(),
+                duration: part.duration,
+                error: part.answer.as_ref().err().map(|e| format!("{:#}", e)),
+            })
+            .collect(),
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Largest integer JSON readers that parse numbers as doubles keep exact.
+const JSON_SAFE_INT: i64 = (1 << 53) - 1;
+
+/// An answer as a JSON value: a number when every reader gets it exactly,
+/// a string otherwise.
+fn json_answer(answer: &Answer) -> String {
+    let exact = match answer {
+        Answer::Int(n) => n.unsigned_abs() <= JSON_SAFE_INT as u64,
+        Answer::BigInt(n) => n.unsigned_abs() <= JSON_SAFE_INT as u128,
+        Answer::Str(_) => false,
+    };
+    if exact {
+        answer.to_string()
+    } else {
+        json_string(&answer.to_string())
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Collects every part run and prints them as a JSON array, one object per
+/// line with a fixed key order.
+pub struct Json {
+    rows: Vec<Row>,
+    parts: Vec<u8>,
+}
+
+impl Json {
+    pub fn new(parts: &[u8]) -> Json {
+        Json {
+            rows: vec![],
+            parts: parts.to_vec(),
+        }
+    }
+}
+
+impl Reporter for Json {
+    fn report(&mut self, result: &DayResult) {
+        self.rows.extend(rows(result, &self.parts));
+    }
+
+    fn finish(&mut self, _elapsed: Duration) {
+        println!("[");
+        for (i, row) in self.rows.iter().enumerate() {
+            let answer = match &row.answer {
+                None => "null".to_string(),
+                Some(answer) => json_answer(answer),
+            };
+            let error = match &row.error {
+                None => "null".to_string(),
+                Some(e) => json_string(e),
+            };
+            let comma = if i + 1 < self.rows.len() { "," } else { "" };
+            println!(
+                "  {{\"day\": {}, \"part\": {}, \"input\": {}, \"answer\": {}, \"duration_ns\": {}, \"error\": {}}}{}",
+                row.day,
+                row.part,
+                json_string(&row.kind.to_string()),
+                answer,
+                row.duration.as_nanos(),
+                error,
+                comma
+            );
+        }
+        println!("]");
+    }
+}
+
+/// Prints every part run as CSV with a header line.
+pub struct Csv {
+    rows: Vec<Row>,
+    parts: Vec<u8>,
+}
+
+impl Csv {
+    pub fn new(parts: &[u8]) -> Csv {
+        Csv {
+            rows: vec![],
+            parts: parts.to_vec(),
+        }
+    }
+}
+
+impl Reporter for Csv {
+    fn report(&mut self, result: &DayResult) {
+        self.rows.extend(rows(result, &self.parts));
+    }
+
+    fn finish(&mut self, _elapsed: Duration) {
+        println!("day,part,input,answer,duration_ns,error");
+        for row in &self.rows {
+            println!(
+                "{},{},{},{},{},{}",
+                row.day,
+                row.part,
+                row.kind,
+                csv_field(
+                    &row.answer
+                        .as_ref()
+                        .map(Answer::to_string)
+                        .unwrap_or_default()
+                ),
+                row.duration.as_nanos(),
+                csv_field(row.error.as_deref().unwrap_or_default()),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_strings_are_escaped() {
+        assert_eq!(json_string("plain"), r#""plain""#);
+        assert_eq!(json_string("say \"hi\"\\"), r#""say \"hi\"\\""#);
+        assert_eq!(json_string("a\nb\tc\r"), r#""a\nb\tc\r""#);
+        assert_eq!(json_string("\u{1}é"), "\"\\u0001é\"");
+    }
+
+    #[test]
+    fn large_answers_are_json_strings() {
+        assert_eq!(json_answer(&Answer::Int(42)), "42");
+        assert_eq!(
+            json_answer(&Answer::Int(-JSON_SAFE_INT)),
+            "-9007199254740991"
+        );
+        assert_eq!(
+            json_answer(&Answer::Int(JSON_SAFE_INT + 1)),
+            r#""9007199254740992""#
+        );
+        assert_eq!(
+            json_answer(&Answer::BigInt(1 << 70)),
+            r#""1180591620717411303424""#
+        );
+        assert_eq!(json_answer(&Answer::BigInt(7)), "7");
+        assert_eq!(json_answer(&"x\"y".into()), r#""x\"y""#);
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_when_needed() {
+        assert_eq!(csv_field("1234"), "1234");
+        assert_eq!(csv_field(""), "");
+        assert_eq!(csv_field("a,b"), r#""a,b""#);
+        assert_eq!(csv_field(r#"say "hi""#), r#""say ""hi""""#);
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+}
@@ -0,0 +1,128 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Result};
+
+use crate::{
+    answer::Answer,
+    input::{InputKind, InputSource},
+    solution::Day,
+};
+
+#[derive(Debug)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Result<Answer>,
+    pub duration: Duration,
+}
+
+/// Everything a run of one day produced. Parts are not run when parsing
+/// fails, in which case `parse` holds the error.
+#[derive(Debug)]
+pub struct DayResult {
+    pub day: u16,
+    pub kind: InputKind,
+    pub parse: Result<Duration>,
+    pub parts: Vec<PartResult>,
+}
+
+impl DayResult {
+    pub fn is_ok(&self) -> bool {
+        self.parse.is_ok() && self.parts.iter().all(|p| p.answer.is_ok())
+    }
+
+    /// Time spent parsing and in every part that ran.
+    pub fn duration(&self) -> Duration {
+        let parse = self.parse.as_ref().copied().unwrap_or_default();
+        parse + self.parts.iter().map(|p| p.duration).sum::<Duration>()
+    }
+}
+
+/// Runs `f`, turning a panic into an error so one failing day does not take
+/// down the others.
+fn catch<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|e| {
+        let msg = e
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| e.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        Err(anyhow!("panicked: {}", msg))
+    })
+}
+
+pub fn run_day(day: &Day, source: &InputSource, kind: InputKind, parts: &[u8]) -> DayResult {
+    let start = Instant::now();
+    let input = match catch(|| day.parse(source, kind)) {
+        Ok(input) => input,
+        Err(e) => {
+            return DayResult {
+                day: day.day,
+                kind,
+                parse: Err(e),
+                parts: vec![],
+            }
+        }
+    };
+    let parse = start.elapsed();
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = catch(|| input.run(part));
+            PartResult {
+                part,
+                answer,
+                duration: start.elapsed(),
+            }
+        })
+        .collect();
+
+    DayResult {
+        day: day.day,
+        kind,
+        parse: Ok(parse),
+        parts,
+    }
+}
+
+/// Runs the days on up to `jobs` threads, returning results in the order of
+/// `days`.
+pub fn run_parallel(
+    days: &[&Day],
+    source: &InputSource,
+    kind: InputKind,
+    parts: &[u8],
+    jobs: usize,
+) -> Vec<DayResult> {
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<DayResult>>> = Mutex::new(days.iter().map(|_| None).collect());
+
+    thread::scope(|s| {
+        for _ in 0..jobs.clamp(1, days.len().max(1)) {
+            s.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(day) = days.get(i) else {
+                    break;
+                };
+                let result = run_day(day, source, kind, parts);
+                results.lock().unwrap()[i] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.expect("every day is run"))
+        .collect()
+}
@@ -0,0 +1,410 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, VecDeque},
+    ops::Add,
+};
+
+use crate::bitgrid::{BitSet, Layout};
+
+/// Edge weights usable with `dijkstra` and `astar`. `Default` is the zero
+/// cost; weights may be zero but must not be negative.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+/// Result of a search: the distance to every reached state and, for each,
+/// all the states it can be reached from on a shortest path. Together the
+/// predecessors form the DAG of all shortest paths, unless zero-cost cycles
+/// make it cyclic.
+#[derive(Debug, Clone)]
+pub struct Paths<S, C> {
+    pub starts: BTreeSet<S>,
+    pub dist: BTreeMap<S, C>,
+    pub pred: BTreeMap<S, Vec<S>>,
+}
+
+impl<S: Ord + Clone, C: Cost> Paths<S, C> {
+    fn new() -> Paths<S, C> {
+        Paths {
+            starts: BTreeSet::new(),
+            dist: BTreeMap::new(),
+            pred: BTreeMap::new(),
+        }
+    }
+
+    fn start(&mut self, state: S) -> bool {
+        if self.dist.contains_key(&state) {
+            return false;
+        }
+        self.dist.insert(state.clone(), C::default());
+        self.pred.insert(state.clone(), vec![]);
+        self.starts.insert(state);
+        true
+    }
+
+    /// Records reaching `to` from `from` at `cost`, returning whether that
+    /// improved on the known distance.
+    fn relax(&mut self, from: &S, to: S, cost: C) -> bool {
+        match self.dist.get(&to) {
+            Some(&d) if d < cost => false,
+            Some(&d) if d == cost => {
+                let pred = self.pred.entry(to).or_default();
+                if !pred.contains(from) {
+                    pred.push(from.clone());
+                }
+                false
+            }
+            _ => {
+                self.dist.insert(to.clone(), cost);
+                self.pred.insert(to, vec![from.clone()]);
+                true
+            }
+        }
+    }
+
+    pub fn distance(&self, state: &S) -> Option<C> {
+        self.dist.get(state).copied()
+    }
+
+    pub fn reached(&self, state: &S) -> bool {
+        self.dist.contains_key(state)
+    }
+
+    /// One shortest path from a start to `target`, both included.
+    pub fn path_to(&self, target: &S) -> Option<Vec<S>> {
+        self.dist.get(target)?;
+        let mut path = vec![target.clone()];
+        let mut state = target;
+        while let Some(prev) = self.pred.get(state).and_then(|p| p.first()) {
+            path.push(prev.clone());
+            state = prev;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every state lying on at least one shortest path to `target`.
+    pub fn on_shortest_paths(&self, target: &S) -> Vec<S> {
+        let mut seen = BTreeSet::new();
+        let mut stack = vec![];
+        if self.reached(target) {
+            stack.push(target.clone());
+        }
+        while let Some(state) = stack.pop() {
+            if !seen.insert(state.clone()) {
+                continue;
+            }
+            stack.extend(self.pred[&state].iter().cloned());
+        }
+        seen.into_iter().collect()
+    }
+
+    /// Number of distinct shortest paths from any start to each state.
+    /// States on or after a zero-cost cycle have infinitely many and are
+    /// left out.
+    pub fn path_counts(&self) -> BTreeMap<S, u64> {
+        // Kahn's algorithm over the predecessor DAG, so every state is
+        // counted after all of its predecessors even when costs tie.
+        let mut succ: BTreeMap<&S, Vec<&S>> = BTreeMap::new();
+        let mut waiting: BTreeMap<&S, usize> = BTreeMap::new();
+        for (state, pred) in &self.pred {
+            waiting.insert(state, pred.len());
+            for p in pred {
+                succ.entry(p).or_default().push(state);
+            }
+        }
+
+        let mut ready: Vec<&S> = waiting
+            .iter()
+            .filter(|&(_, &n)| n == 0)
+            .map(|(&s, _)| s)
+            .collect();
+        let mut counts: BTreeMap<S, u64> = BTreeMap::new();
+        while let Some(state) = ready.pop() {
+            let from_start = self.starts.contains(state) as u64;
+            let count = from_start + self.pred[state].iter().map(|p| counts[p]).sum::<u64>();
+            counts.insert(state.clone(), count);
+            for &next in succ.get(state).into_iter().flatten() {
+                let n = waiting.get_mut(next).expect("successor is a state");
+                *n -= 1;
+                if *n == 0 {
+                    ready.push(next);
+                }
+            }
+        }
+        counts
+    }
+}
+
+/// Breadth-first search from every state of `starts` at once, each step
+/// costing 1.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+) -> Paths<S, usize>
+where
+    S: Ord + Clone,
+    I: IntoIterator<Item = S>,
+{
+    let mut paths = Paths::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if paths.start(start.clone()) {
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(state) = queue.pop_front() {
+        let next = paths.dist[&state] + 1;
+        for u in neighbours(&state) {
+            if paths.relax(&state, u.clone(), next) {
+                queue.push_back(u);
+            }
+        }
+    }
+    paths
+}
+
+/// Marks in `seen` every state reachable from `starts`, for when only
+/// reachability matters and the states fit a dense `BitSet`. States already
+/// in `seen` are treated as visited. Returns how many states were added.
+pub fn flood<L, I>(
+    starts: impl IntoIterator<Item = L::Key>,
+    mut neighbours: impl FnMut(&L::Key) -> I,
+    seen: &mut BitSet<L>,
+) -> usize
+where
+    L: Layout,
+    L::Key: Clone,
+    I: IntoIterator<Item = L::Key>,
+{
+    let mut stack = vec![];
+    for start in starts {
+        if seen.insert(start.clone()) {
+            stack.push(start);
+        }
+    }
+
+    let mut added = stack.len();
+    while let Some(state) = stack.pop() {
+        for u in neighbours(&state) {
+            if seen.insert(u.clone()) {
+                added += 1;
+                stack.push(u);
+            }
+        }
+    }
+    added
+}
+
+/// Dijkstra's algorithm from every state of `starts` at once, `neighbours`
+/// yielding each successor with the cost of the step to it.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+) -> Paths<S, C>
+where
+    S: Ord + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut paths = Paths::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        if paths.start(start.clone()) {
+            heap.push(Reverse((C::default(), start)));
+        }
+    }
+
+    while let Some(Reverse((cost, state))) = heap.pop() {
+        if paths.dist[&state] < cost {
+            continue;
+        }
+        for (u, step) in neighbours(&state) {
+            let next = cost + step;
+            if paths.relax(&state, u.clone(), next) {
+                heap.push(Reverse((next, u)));
+            }
+        }
+    }
+    paths
+}
+
+/// A* search for the cheapest path from `starts` to a state satisfying
+/// `goal`. `heuristic` must never overestimate the remaining cost. Returns
+/// the cost and the path, both ends included.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut goal: impl FnMut(&S) -> bool,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+) -> Option<(C, Vec<S>)>
+where
+    S: Ord + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut paths = Paths::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        if paths.start(start.clone()) {
+            heap.push(Reverse((heuristic(&start), C::default(), start)));
+        }
+    }
+
+    while let Some(Reverse((_, cost, state))) = heap.pop() {
+        if paths.dist[&state] < cost {
+            continue;
+        }
+        if goal(&state) {
+            return Some((cost, paths.path_to(&state)?));
+        }
+        for (u, step) in neighbours(&state) {
+            let next = cost + step;
+            if paths.relax(&state, u.clone(), next) {
+                heap.push(Reverse((next + heuristic(&u), next, u)));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Successors in a graph given as `(from, to, cost)` edges.
+    fn edges(list: &[(u32, u32, u32)]) -> impl Fn(&u32) -> Vec<(u32, u32)> + '_ {
+        move |&s| {
+            list.iter()
+                .filter(|&&(from, _, _)| from == s)
+                .map(|&(_, to, cost)| (to, cost))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn bfs_distances_and_paths() {
+        // 0 - 1 - 2 - 3, plus a shortcut 0 - 2.
+        let adj = |&s: &u32| match s {
+            0 => vec![1, 2],
+            1 => vec![0, 2],
+            2 => vec![1, 3],
+            _ => vec![2],
+        };
+        let paths = bfs([0], adj);
+        assert_eq!(paths.distance(&3), Some(2));
+        assert_eq!(paths.path_to(&3), Some(vec![0, 2, 3]));
+        assert_eq!(paths.path_to(&7), None);
+        assert_eq!(paths.on_shortest_paths(&3), vec![0, 2, 3]);
+    }
+
+    #[test]
+    fn path_counts_on_a_grid() {
+        // Every monotone path across a 3x3 grid: C(4, 2) = 6.
+        let adj = |&(x, y): &(u8, u8)| {
+            let mut next = vec![];
+            if x < 2 {
+                next.push((x + 1, y));
+            }
+            if y < 2 {
+                next.push((x, y + 1));
+            }
+            next
+        };
+        let counts = bfs([(0, 0)], adj).path_counts();
+        assert_eq!(counts[&(2, 2)], 6);
+        assert_eq!(counts[&(1, 1)], 2);
+        assert_eq!(counts[&(0, 0)], 1);
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheaper_longer_paths() {
+        let graph = [(0, 1, 10), (0, 2, 1), (2, 3, 1), (3, 1, 1)];
+        let paths = dijkstra([0], edges(&graph));
+        assert_eq!(paths.distance(&1), Some(3));
+        assert_eq!(paths.path_to(&1), Some(vec![0, 2, 3, 1]));
+    }
+
+    #[test]
+    fn path_counts_with_zero_cost_edges() {
+        let graph = [(0, 2, 0), (2, 1, 0)];
+        let counts = dijkstra([0], edges(&graph)).path_counts();
+        assert_eq!(counts, BTreeMap::from([(0, 1), (1, 1), (2, 1)]));
+
+        let diamond = [(0, 1, 0), (0, 2, 0), (1, 3, 0), (2, 3, 0), (3, 4, 5)];
+        let counts = dijkstra([0], edges(&diamond)).path_counts();
+        assert_eq!(counts[&3], 2);
+        assert_eq!(counts[&4], 2);
+    }
+
+    #[test]
+    fn path_counts_from_several_starts() {
+        // 1 is a start and also reachable from start 0 at no cost.
+        let graph = [(0, 1, 0), (1, 2, 1)];
+        let counts = dijkstra([0, 1], edges(&graph)).path_counts();
+        assert_eq!(counts[&1], 2);
+        assert_eq!(counts[&2], 2);
+    }
+
+    #[test]
+    fn path_counts_skip_zero_cost_cycles() {
+        let graph = [(0, 1, 0), (1, 0, 0), (1, 2, 1), (3, 4, 1)];
+        let counts = dijkstra([0, 3], edges(&graph)).path_counts();
+        assert_eq!(counts, BTreeMap::from([(3, 1), (4, 1)]));
+    }
+
+    #[test]
+    fn astar_matches_dijkstra() {
+        // A 5x5 grid with a wall in column 2 open only at the bottom.
+        let wall = |(x, y): (i32, i32)| y == 2 && x < 4;
+        let adj = |&(x, y): &(i32, i32)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|&(x, y)| (0..5).contains(&x) && (0..5).contains(&y))
+                .filter(|&p| !wall(p))
+                .map(|p| (p, 1))
+                .collect::<Vec<_>>()
+        };
+        let goal = (0, 4);
+        let (cost, path) = astar(
+            [(0, 0)],
+            |&p| p == goal,
+            adj,
+            |&(x, y)| (goal.0 - x).abs() + (goal.1 - y).abs(),
+        )
+        .unwrap();
+        assert_eq!(Some(cost), dijkstra([(0, 0)], adj).distance(&goal));
+        assert_eq!(cost, 12);
+        assert_eq!(path.len(), 13);
+        assert_eq!((path[0], path[12]), ((0, 0), goal));
+    }
+
+    #[test]
+    fn astar_without_a_way_through() {
+        let graph = [(0, 1, 1)];
+        assert_eq!(astar([0], |&s| s == 2, edges(&graph), |_| 0), None);
+    }
+
+    #[test]
+    fn flood_fills_a_bitgrid() {
+        use crate::{bitgrid::BitGrid, geom::Point, grid::Grid};
+
+        let grid: Grid<char> = "..#.\n.##.\n...#".parse().unwrap();
+        let open = |&p: &Point| {
+            grid.neighbours4(p)
+                .filter(|&q| grid[q] == '.')
+                .collect::<Vec<_>>()
+        };
+        let mut seen = BitGrid::new(3, 4);
+        assert_eq!(flood([Point::new(0, 0)], open, &mut seen), 6);
+        assert!(seen.contains(Point::new(2, 2)));
+        assert!(!seen.contains(Point::new(1, 3)));
+
+        // States already seen block the way, and are not counted again.
+        let mut seen = BitGrid::new(3, 4);
+        seen.insert(Point::new(2, 1));
+        assert_eq!(flood([Point::new(0, 0)], open, &mut seen), 4);
+        assert_eq!(flood([Point::new(0, 0)], open, &mut seen), 0);
+    }
+}
@@ -0,0 +1,66 @@
+use anyhow::{bail, Result};
+
+use crate::{
+    answer::Answer,
+    input::{InputKind, InputSource, Reader},
+};
+
+/// A single day's puzzle: the input is parsed once and shared by both parts.
+pub trait Solution {
+    type Input;
+
+    fn parse(reader: Reader) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Answer>;
+    fn part2(input: &Self::Input) -> Result<Answer>;
+}
+
+/// Parsed input of some day, with the concrete `Solution` erased.
+pub trait Parsed {
+    fn part1(&self) -> Result<Answer>;
+    fn part2(&self) -> Result<Answer>;
+}
+
+impl dyn Parsed {
+    pub fn run(&self, part: u8) -> Result<Answer> {
+        match part {
+            1 => self.part1(),
+            2 => self.part2(),
+            _ => bail!("invalid part {}", part),
+        }
+    }
+}
+
+struct Input<S: Solution>(S::Input);
+
+impl<S: Solution> Parsed for Input<S> {
+    fn part1(&self) -> Result<Answer> {
+        S::part1(&self.0)
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        S::part2(&self.0)
+    }
+}
+
+fn parse<S: Solution + 'static>(reader: Reader) -> Result<Box<dyn Parsed>> {
+    Ok(Box::new(Input::<S>(S::parse(reader)?)))
+}
+
+/// Registry entry tying a day number to its `Solution`.
+pub struct Day {
+    pub day: u16,
+    parse: fn(Reader) -> Result<Box<dyn Parsed>>,
+}
+
+impl Day {
+    pub const fn new<S: Solution + 'static>(day: u16) -> Day {
+        Day {
+            day,
+            parse: parse::<S>,
+        }
+    }
+
+    pub fn parse(&self, source: &InputSource, kind: InputKind) -> Result<Box<dyn Parsed>> {
+        (self.parse)(source.open(self.day, kind)?)
+    }
+}
@@ -0,0 +1,178 @@
+use crate::grid::{Grid, Pos};
+
+/// Disjoint sets over `0..n`, with path compression and union by size.
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl UnionFind {
+    /// `n` singleton sets.
+    pub fn new(n: usize) -> UnionFind {
+        UnionFind {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            count: n,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Representative of the set containing `x`.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut x = x;
+        while self.parent[x] != root {
+            let next = self.parent[x];
+            self.parent[x] = root;
+            x = next;
+        }
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`, returning `false` if they
+    /// already were the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            (a, b) = (b, a);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.count -= 1;
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Size of the set containing `x`.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Number of disjoint sets.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Every set, each sorted, ordered by their smallest element.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let labels = self.labels();
+        let mut components = vec![vec![]; self.count];
+        for (x, label) in labels.into_iter().enumerate() {
+            components[label].push(x);
+        }
+        components
+    }
+
+    /// Component number of each element, numbered `0..count()` in order of
+    /// their smallest element.
+    pub fn labels(&mut self) -> Vec<usize> {
+        let mut label_of_root = vec![usize::MAX; self.len()];
+        let mut next = 0;
+        (0..self.len())
+            .map(|x| {
+                let root = self.find(x);
+                if label_of_root[root] == usize::MAX {
+                    label_of_root[root] = next;
+                    next += 1;
+                }
+                label_of_root[root]
+            })
+            .collect()
+    }
+}
+
+/// Labels the orthogonally connected components of `grid`, two neighbouring
+/// cells being connected when `connected` says so. Components are numbered
+/// `0..count` in reading order of their first cell; returns the labels and
+/// `count`.
+pub fn grid_components<T>(
+    grid: &Grid<T>,
+    mut connected: impl FnMut(&T, &T) -> bool,
+) -> (Grid<usize>, usize) {
+    let mut sets = UnionFind::new(grid.height() * grid.width());
+    let index = |p: Pos| p.x as usize * grid.width() + p.y as usize;
+
+    for (pos, cell) in grid.iter() {
+        // Right and down are enough to see every pair of neighbours once.
+        for next in [pos + Pos::new(0, 1), pos + Pos::new(1, 0)] {
+            if let Some(other) = grid.get(next) {
+                if connected(cell, other) {
+                    sets.union(index(pos), index(next));
+                }
+            }
+        }
+    }
+
+    let count = sets.count();
+    let labels = Grid::new(grid.height(), grid.width(), sets.labels());
+    (labels, count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_tracks_sizes_and_count() {
+        let mut sets = UnionFind::new(6);
+        assert_eq!(sets.count(), 6);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+        assert_eq!(sets.count(), 3);
+        assert_eq!(sets.size(2), 4);
+        assert_eq!(sets.size(5), 1);
+        assert!(sets.same(0, 3));
+        assert!(!sets.same(0, 4));
+    }
+
+    #[test]
+    fn labels_follow_smallest_element() {
+        let mut sets = UnionFind::new(5);
+        sets.union(4, 1);
+        sets.union(3, 2);
+        sets.union(2, 0);
+        assert_eq!(sets.labels(), vec![0, 1, 0, 0, 1]);
+        assert_eq!(sets.components(), vec![vec![0, 2, 3], vec![1, 4]]);
+    }
+
+    #[test]
+    fn empty() {
+        let mut sets = UnionFind::new(0);
+        assert!(sets.is_empty());
+        assert_eq!(sets.count(), 0);
+        assert!(sets.components().is_empty());
+    }
+
+    #[test]
+    fn grid_components_are_orthogonal() {
+        let grid: Grid<char> = "AAB\nABB\nBAA".parse().unwrap();
+        let (labels, count) = grid_components(&grid, |a, b| a == b);
+        // The B in the corner touches the other Bs only diagonally.
+        assert_eq!(count, 4);
+        assert_eq!(
+            labels.rows().collect::<Vec<_>>(),
+            vec![&[0, 0, 1][..], &[0, 1, 1], &[2, 3, 3]]
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use std::{collections::BTreeMap, fmt::Display, fs, path::Path};
+
+use anyhow::{bail, Context, Result};
+
+use crate::{answer::Answer, input::InputKind};
+
+/// Name of the expected-answers file stored next to a day's inputs.
+pub const MANIFEST: &str = "answers.toml";
+
+/// Known answers of a day, read from `answers.toml`:
+///
+/// ```toml
+/// [input]
+/// part1 = 3714264
+/// part2 = 18805872
+///
+/// [sample]
+/// part1 = 11
+/// ```
+///
+/// Only tables named after an `InputKind` with integer or string `part1`
+/// and `part2` keys are understood.
+#[derive(Debug, Default)]
+pub struct Manifest {
+    answers: BTreeMap<InputKind, [Option<String>; 2]>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing,
+    Error(String),
+}
+
+fn parse_value(value: &str) -> Result<String> {
+    if let Some(s) = value.strip_prefix('"') {
+        match s.strip_suffix('"') {
+            Some(s) => Ok(s.to_string()),
+            None => bail!("unterminated string {}", value),
+        }
+    } else {
+        let n = value.replace('_', "");
+        n.parse::<i128>()
+            .with_context(|| format!("invalid value {}", value))?;
+        Ok(n)
+    }
+}
+
+/// `line` up to its `#` comment, if any. A `#` inside a quoted string
+/// is part of the string.
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+impl Manifest {
+    /// Loads a manifest, treating a missing file as an empty one.
+    pub fn load(path: &Path) -> Result<Manifest> {
+        if !path.is_file() {
+            return Ok(Manifest::default());
+        }
+        let s = fs::read_to_string(path)?;
+        Manifest::parse(&s).with_context(|| format!("invalid manifest {}", path.display()))
+    }
+
+    pub fn parse(s: &str) -> Result<Manifest> {
+        let mut manifest = Manifest::default();
+        let mut table = None;
+
+        for (lineno, line) in s.lines().enumerate() {
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let err = || format!("line {}: `{}`", lineno + 1, line);
+
+            if let Some(name) = line.strip_prefix('[') {
+                let name = name.strip_suffix(']').with_context(err)?;
+                let kind = name.trim().parse::<InputKind>().with_context(err)?;
+                manifest.answers.entry(kind).or_default();
+                table = Some(kind);
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').with_context(err)?;
+            let kind = table.with_context(err)?;
+            let part = match key.trim() {
+                "part1" => 0,
+                "part2" => 1,
+                key => bail!("{}: unknown key `{}`", err(), key),
+            };
+            let value = parse_value(value.trim()).with_context(err)?;
+            manifest.answers.entry(kind).or_default()[part] = Some(value);
+        }
+
+        Ok(manifest)
+    }
+
+    /// Input kinds with a table in the manifest, the real input always included.
+    pub fn kinds(&self) -> Vec<InputKind> {
+        let mut kinds: Vec<InputKind> = self.answers.keys().cloned().collect();
+        if !kinds.contains(&InputKind::Real) {
+            kinds.insert(0, InputKind::Real);
+        }
+        kinds
+    }
+
+    pub fn expected(&self, kind: InputKind, part: u8) -> Option<&str> {
+        let answers = self.answers.get(&kind)?;
+        answers[part as usize - 1].as_deref()
+    }
+
+    pub fn check(&self, kind: InputKind, part: u8, actual: &Result<Answer>) -> Status {
+        match (self.expected(kind, part), actual) {
+            (_, Err(e)) => Status::Error(e.to_string()),
+            (None, Ok(_)) => Status::Missing,
+            (Some(expected), Ok(actual)) if actual.to_string() == expected => Status::Pass,
+            (Some(expected), Ok(actual)) => Status::Fail {
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            },
+        }
+    }
+}
+
+impl Status {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Status::Fail { .. } | Status::Error(_))
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Pass => f.pad("pass"),
+            Status::Fail { .. } => f.pad("FAIL"),
+            Status::Missing => f.pad("missing"),
+            Status::Error(_) => f.pad("ERROR"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use super::*;
+
+    const SAMPLE: &str = r#"
+# answers for day 1
+[input]
+part1 = 3_714_264  # checked
+part2 = "a#b"
+
+[ sample2 ]
+part1 = "LGYHWDKMV"
+"#;
+
+    #[test]
+    fn parses_tables_quotes_and_comments() {
+        let manifest = Manifest::parse(SAMPLE).unwrap();
+        assert_eq!(manifest.expected(InputKind::Real, 1), Some("3714264"));
+        assert_eq!(manifest.expected(InputKind::Real, 2), Some("a#b"));
+        assert_eq!(
+            manifest.expected(InputKind::Sample(2), 1),
+            Some("LGYHWDKMV")
+        );
+        assert_eq!(manifest.expected(InputKind::Sample(2), 2), None);
+        assert_eq!(manifest.expected(InputKind::Sample(1), 1), None);
+        assert_eq!(
+            manifest.kinds(),
+            vec![InputKind::Real, InputKind::Sample(2)]
+        );
+    }
+
+    #[test]
+    fn real_input_is_always_checked() {
+        let manifest = Manifest::parse("[sample]\npart1 = 11").unwrap();
+        assert_eq!(
+            manifest.kinds(),
+            vec![InputKind::Real, InputKind::Sample(1)]
+        );
+        assert!(Manifest::parse("").unwrap().answers.is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        for bad in [
+            "part1 = 1",
+            "[input]\npart3 = 1",
+            "[input]\npart1 = 1x",
+            "[input]\npart1 = \"open",
+            "[input\npart1 = 1",
+            "[bogus]",
+            "[input]\npart1",
+        ] {
+            assert!(Manifest::parse(bad).is_err(), "{:?} parsed", bad);
+        }
+        let err = Manifest::parse("[input]\n\npart3 = 1").unwrap_err();
+        assert!(err.to_string().contains("line 3"), "{}", err);
+    }
+
+    #[test]
+    fn check_outcomes() {
+        let manifest = Manifest::parse(SAMPLE).unwrap();
+        let real = InputKind::Real;
+        assert_eq!(
+            manifest.check(real, 1, &Ok(Answer::Int(3714264))),
+            Status::Pass
+        );
+        assert_eq!(manifest.check(real, 2, &Ok("a#b".into())), Status::Pass);
+        assert_eq!(
+            manifest.check(real, 1, &Ok(Answer::Int(1))),
+            Status::Fail {
+                expected: "3714264".to_string(),
+                actual: "1".to_string(),
+            }
+        );
+        // Answers are compared as text, so digits match either way.
+        assert_eq!(manifest.check(real, 1, &Ok("3714264".into())), Status::Pass);
+        let sample = InputKind::Sample(2);
+        assert!(manifest.check(sample, 1, &Ok(Answer::Int(7))).is_failure());
+        assert_eq!(
+            manifest.check(sample, 2, &Ok(Answer::Int(7))),
+            Status::Missing
+        );
+        let error = manifest.check(real, 1, &Err(anyhow!("boom")));
+        assert_eq!(error, Status::Error("boom".to_string()));
+        assert!(error.is_failure());
+        assert!(!Status::Missing.is_failure());
+        assert_eq!(format!("{:<5}|", Status::Pass), "pass |");
+    }
+}